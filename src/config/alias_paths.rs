@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The `paths` of tsconfig.json, mapping aliases to paths relative to the project root. Only the
+/// first path of each alias is used.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AliasPaths {
+    pub paths: BTreeMap<String, Vec<String>>,
+}
+
+impl AliasPaths {
+    /// Whether the specifier is matched by an alias, either exactly or by a pattern with `*`.
+    pub fn matches(&self, specifier: &str) -> bool {
+        self.paths.keys().any(|key| match key.split_once('*') {
+            Some((prefix, suffix)) => {
+                specifier.len() >= prefix.len() + suffix.len()
+                    && specifier.starts_with(prefix)
+                    && specifier.ends_with(suffix)
+            }
+            None => key == specifier,
+        })
+    }
+
+    /// The aliases of directories like `"@/*": ["./src/*"]`, as pairs of the alias prefix and the
+    /// segments of the directory. Other aliases can not be used to rewrite specifiers.
+    pub fn directories(&self) -> impl Iterator<Item = (&str, Vec<&str>)> {
+        self.paths.iter().filter_map(|(key, paths)| {
+            let prefix = key.strip_suffix('*')?;
+            let directory = paths.first()?.strip_suffix('*')?;
+            if prefix.contains('*') || directory.contains('*') {
+                return None;
+            }
+
+            let segments: Vec<_> = directory
+                .split('/')
+                .filter(|it| !matches!(*it, "" | "."))
+                .collect();
+
+            // The project root itself, or a directory out of it, can not be located
+            if segments.is_empty() || segments.contains(&"..") {
+                return None;
+            }
+
+            Some((prefix, segments))
+        })
+    }
+}
+
+/// When to rewrite between relative and alias specifiers, using the directories in `aliasPaths`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AliasRewrite {
+    /// Rewrites the relative specifiers climbing more parent directories than this to aliases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_depth: Option<usize>,
+    /// Rewrites the aliases of the modules in the directory of the importing file to `./`.
+    #[serde(default)]
+    pub same_directory: bool,
+}
+
+impl AliasRewrite {
+    pub fn is_enabled(&self) -> bool {
+        self.parent_depth.is_some() || self.same_directory
+    }
+}
@@ -1,22 +1,128 @@
 use anyhow::{bail, Error};
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 
+use crate::utils::package_name;
+
+use super::{Glob, RawRule, RuleObject};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(try_from = "String", into = "String")]
-pub struct CustomRule(pub Regex);
+#[serde(try_from = "RawRule", into = "RawRule")]
+pub enum CustomRule {
+    Regex { regex: Regex, flags: String },
+    Glob(Glob),
+    Packages(Vec<String>),
+    Prefix(String),
+}
 
 impl CustomRule {
+    pub fn regex(pattern: &str, flags: &str) -> Result<Self, Error> {
+        if pattern.is_empty() {
+            bail!("invalid regex pattern")
+        }
+
+        let mut builder = RegexBuilder::new(pattern);
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'x' => builder.ignore_whitespace(true),
+                'U' => builder.swap_greed(true),
+                // Unicode is always enabled
+                'u' => &mut builder,
+                x => bail!("unsupported regex flag `{x}`"),
+            };
+        }
+
+        Ok(CustomRule::Regex {
+            regex: builder.build()?,
+            flags: flags.to_string(),
+        })
+    }
+
     pub fn matches(&self, module: &str) -> bool {
-        self.0.is_match(module)
+        match self {
+            CustomRule::Regex { regex, .. } => regex.is_match(module),
+            CustomRule::Glob(glob) => glob.matches(module),
+            CustomRule::Packages(packages) => {
+                let name = package_name(module);
+                packages.iter().any(|it| it == name)
+            }
+            CustomRule::Prefix(prefix) => module.starts_with(prefix),
+        }
     }
 }
 
-impl TryFrom<String> for CustomRule {
+impl TryFrom<RawRule> for CustomRule {
     type Error = Error;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        Self::try_from(&*value)
+    fn try_from(value: RawRule) -> Result<Self, Self::Error> {
+        match value {
+            RawRule::String(value) => Self::try_from(&*value),
+            RawRule::Object(value) => Self::try_from(value),
+        }
+    }
+}
+
+impl TryFrom<RuleObject> for CustomRule {
+    type Error = Error;
+
+    fn try_from(value: RuleObject) -> Result<Self, Self::Error> {
+        match value {
+            RuleObject {
+                regex: Some(regex),
+                flags,
+                packages: None,
+                prefix: None,
+                all: None,
+                any: None,
+                not: None,
+            } => Self::regex(&regex, flags.as_deref().unwrap_or_default()),
+            RuleObject {
+                regex: None,
+                flags: None,
+                packages: Some(packages),
+                prefix: None,
+                all: None,
+                any: None,
+                not: None,
+            } => {
+                if packages.is_empty() {
+                    bail!("`packages` must not be empty");
+                }
+
+                Ok(CustomRule::Packages(packages))
+            }
+            RuleObject {
+                regex: None,
+                flags: None,
+                packages: None,
+                prefix: Some(prefix),
+                all: None,
+                any: None,
+                not: None,
+            } => {
+                if prefix.is_empty() {
+                    bail!("`prefix` must not be empty");
+                }
+
+                Ok(CustomRule::Prefix(prefix))
+            }
+            RuleObject { all: Some(_), .. }
+            | RuleObject { any: Some(_), .. }
+            | RuleObject { not: Some(_), .. } => {
+                bail!("`all`, `any` and `not` are not allowed here")
+            }
+            RuleObject {
+                flags: Some(_),
+                regex: None,
+                ..
+            } => {
+                bail!("`flags` can only be used together with `regex`")
+            }
+            _ => bail!("a rule object must have exactly one of `regex`, `packages` or `prefix`"),
+        }
     }
 }
 
@@ -28,17 +134,35 @@ impl TryFrom<&str> for CustomRule {
             bail!("invalid rule");
         }
 
-        if value.is_empty() {
-            bail!("invalid regex pattern")
+        if let Some(pattern) = value.strip_prefix("glob:") {
+            let glob = Glob::new(pattern)?;
+            return Ok(CustomRule::Glob(glob));
         }
 
-        let re = Regex::new(value)?;
-        Ok(CustomRule(re))
+        Self::regex(value, "")
     }
 }
 
-impl From<CustomRule> for String {
+impl From<CustomRule> for RawRule {
     fn from(value: CustomRule) -> Self {
-        value.0.to_string()
+        match value {
+            CustomRule::Regex { regex, flags } if flags.is_empty() => {
+                RawRule::String(regex.to_string())
+            }
+            CustomRule::Regex { regex, flags } => RawRule::Object(RuleObject {
+                regex: Some(regex.to_string()),
+                flags: Some(flags),
+                ..Default::default()
+            }),
+            CustomRule::Glob(glob) => RawRule::String(format!("glob:{}", glob.pattern())),
+            CustomRule::Packages(packages) => RawRule::Object(RuleObject {
+                packages: Some(packages),
+                ..Default::default()
+            }),
+            CustomRule::Prefix(prefix) => RawRule::Object(RuleObject {
+                prefix: Some(prefix),
+                ..Default::default()
+            }),
+        }
     }
 }
@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use dprint_core::configuration::ConfigKeyMap;
+use serde_json::{json, Map, Value};
+
+use super::{resolve_config, Configuration, Glob};
+
+const KNOWN_OPTIONS: [&str; 11] = [
+    "groups",
+    "pathGroups",
+    "pathGroupsExcludedImportTypes",
+    "distinctGroup",
+    "newlines-between",
+    "newlines-between-types",
+    "alphabetize",
+    "named",
+    "warnOnUnassignedImports",
+    "sortTypesGroup",
+    "consolidateIslands",
+];
+
+const DEFAULT_GROUPS: [&str; 5] = ["builtin", "external", "parent", "sibling", "index"];
+const DEFAULT_EXCLUDED_TYPES: [&str; 3] = ["builtin", "external", "object"];
+
+/// The imporg options converted from the options of `import/order` in eslint-plugin-import.
+#[derive(Debug)]
+pub struct ImportOrderConversion {
+    pub options: Map<String, Value>,
+    /// Describes the options that could not be converted exactly.
+    pub warnings: Vec<String>,
+}
+
+/// Converts the options of `import/order` in eslint-plugin-import into imporg options.
+pub fn convert_import_order(options: &Value) -> ImportOrderConversion {
+    let mut converter = Converter::default();
+    let options = converter.convert(options);
+
+    ImportOrderConversion {
+        options,
+        warnings: converter.warnings,
+    }
+}
+
+/// Converts the options of `import/order` in eslint-plugin-import into an imporg config, with the
+/// warnings for the options that could not be converted exactly.
+pub fn migrate_import_order(options: &Value) -> Result<(Configuration, Vec<String>)> {
+    let conversion = convert_import_order(options);
+
+    let mut raw: ConfigKeyMap = serde_json::from_value(Value::Object(conversion.options))?;
+    let mut diagnostics = Vec::new();
+    let config = resolve_config(&mut raw, &mut diagnostics)
+        .map_err(|err| anyhow!("{}: {}", err.property_name, err.message))?;
+
+    let mut warnings = conversion.warnings;
+    warnings.extend(diagnostics.into_iter().map(|it| it.message));
+
+    Ok((config, warnings))
+}
+
+/// The rules of the imports placed before, in, and after a group of `import/order`.
+#[derive(Default)]
+struct GroupParts {
+    before: Vec<Value>,
+    include: Vec<Value>,
+    after: Vec<Value>,
+}
+
+#[derive(Default)]
+struct Converter {
+    warnings: Vec<String>,
+}
+
+impl Converter {
+    fn warn(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+
+    fn convert(&mut self, options: &Value) -> Map<String, Value> {
+        let mut output = Map::new();
+
+        let Some(options) = options.as_object() else {
+            self.warn("the options of `import/order` must be an object");
+            return output;
+        };
+
+        for key in options.keys() {
+            if !KNOWN_OPTIONS.contains(&key.as_str()) {
+                self.warn(format!("unknown option `{key}`"));
+            }
+        }
+
+        for key in [
+            "named",
+            "warnOnUnassignedImports",
+            "sortTypesGroup",
+            "consolidateIslands",
+        ] {
+            if options.get(key).is_some_and(|it| it != &Value::Bool(false)) {
+                self.warn(format!("`{key}` is not supported"));
+            }
+        }
+
+        if options.contains_key("newlines-between-types") {
+            self.warn("`newlines-between-types` is not supported");
+        }
+
+        let (mut groups, type_groups) = self.convert_groups(options.get("groups"));
+        let path_groups = self.convert_path_groups(options, &mut groups, &type_groups);
+
+        // Type imports are placed in the `type` group whatever module they import
+        let type_rule = json!("<type>");
+        let has_type = type_groups.contains_key("type");
+
+        let groups = groups
+            .into_iter()
+            .flat_map(|group| {
+                let before = group.before.into_iter().map(|rule| vec![rule]);
+                let after = group.after.into_iter().map(|rule| vec![rule]);
+                before.chain([group.include]).chain(after)
+            })
+            .filter(|include| !include.is_empty())
+            .map(|include| match has_type && !include.contains(&type_rule) {
+                true => json!({ "include": include, "exclude": [type_rule] }),
+                false => json!({ "include": include }),
+            })
+            .collect();
+
+        output.insert("groups".to_string(), Value::Array(groups));
+
+        // Path groups are matched before the groups of import types
+        if path_groups {
+            output.insert("matchStrategy".to_string(), json!("specificity"));
+        }
+
+        let separate_groups = self.convert_newlines(options.get("newlines-between"));
+        output.insert("separateGroups".to_string(), json!(separate_groups));
+
+        let sort_order = self.convert_alphabetize(options.get("alphabetize"));
+        output.insert("sortOrder".to_string(), json!(sort_order));
+
+        output
+    }
+
+    /// Returns the groups, and the index of the group each import type is placed in.
+    fn convert_groups(
+        &mut self,
+        value: Option<&Value>,
+    ) -> (Vec<GroupParts>, HashMap<String, usize>) {
+        let default = DEFAULT_GROUPS.map(Value::from).to_vec();
+        let items = match value {
+            None => &default,
+            Some(Value::Array(items)) => items,
+            Some(_) => {
+                self.warn("`groups` must be an array, using the default groups instead");
+                &default
+            }
+        };
+
+        let mut groups = Vec::new();
+        let mut type_groups = HashMap::new();
+
+        for (index, item) in items.iter().enumerate() {
+            let types = match item {
+                Value::String(it) => vec![it.as_str()],
+                Value::Array(it) => it.iter().filter_map(Value::as_str).collect(),
+                _ => {
+                    self.warn(format!("`groups[{index}]` must be a string or an array"));
+                    continue;
+                }
+            };
+
+            let mut group = GroupParts::default();
+
+            for kind in types {
+                type_groups.entry(kind.to_string()).or_insert(groups.len());
+
+                let Some(rule) = self.convert_type(kind) else {
+                    continue;
+                };
+
+                if !group.include.contains(&rule) {
+                    group.include.push(rule);
+                }
+            }
+
+            groups.push(group);
+        }
+
+        (groups, type_groups)
+    }
+
+    fn convert_type(&mut self, kind: &str) -> Option<Value> {
+        let rule = match kind {
+            "builtin" => "<builtin>",
+            "external" => "<npm>",
+            "internal" => "<alias>",
+            "parent" => "<parent>",
+            "sibling" => "<sibling>",
+            "index" => "<index>",
+            "unknown" => "<unmatched>",
+            "object" => {
+                self.warn("`object` is not supported, `import x = require()` is never reordered");
+                return None;
+            }
+            "type" => "<type>",
+            _ => {
+                self.warn(format!("unknown import type `{kind}`"));
+                return None;
+            }
+        };
+
+        Some(json!(rule))
+    }
+
+    /// Returns whether any path group is converted.
+    fn convert_path_groups(
+        &mut self,
+        options: &Map<String, Value>,
+        groups: &mut [GroupParts],
+        type_groups: &HashMap<String, usize>,
+    ) -> bool {
+        let Some(value) = options.get("pathGroups") else {
+            return false;
+        };
+
+        let Some(items) = value.as_array() else {
+            self.warn("`pathGroups` must be an array");
+            return false;
+        };
+
+        let excluded: Vec<_> = match options.get("pathGroupsExcludedImportTypes") {
+            Some(Value::Array(it)) => it.iter().filter_map(Value::as_str).collect(),
+            _ => DEFAULT_EXCLUDED_TYPES.to_vec(),
+        };
+
+        // Types with no corresponding rule never match anything anyway
+        let excluded: Vec<_> = excluded
+            .into_iter()
+            .filter_map(|kind| match kind {
+                "builtin" => Some(json!("<builtin>")),
+                "external" => Some(json!("<npm>")),
+                "internal" => Some(json!("<alias>")),
+                "parent" => Some(json!("<parent>")),
+                "sibling" => Some(json!("<sibling>")),
+                "index" => Some(json!("<index>")),
+                _ => None,
+            })
+            .collect();
+
+        let mut converted = false;
+        let mut positioned = false;
+
+        for (index, item) in items.iter().enumerate() {
+            let Some(pattern) = item.get("pattern").and_then(Value::as_str) else {
+                self.warn(format!("`pathGroups[{index}]` has no `pattern`"));
+                continue;
+            };
+
+            if let Err(err) = Glob::new(pattern) {
+                self.warn(format!("`pathGroups[{index}]`: {err}"));
+                continue;
+            }
+
+            if item.get("patternOptions").is_some() {
+                self.warn(format!(
+                    "`pathGroups[{index}].patternOptions` is not supported"
+                ));
+            }
+
+            let Some(kind) = item.get("group").and_then(Value::as_str) else {
+                self.warn(format!("`pathGroups[{index}]` has no `group`"));
+                continue;
+            };
+
+            let Some(&target) = type_groups.get(kind) else {
+                self.warn(format!(
+                    "`pathGroups[{index}]`: `{kind}` is not listed in `groups`"
+                ));
+                continue;
+            };
+
+            let glob = json!(format!("glob:{pattern}"));
+            let rule = match excluded.is_empty() {
+                true => glob,
+                false => json!({ "all": [glob, { "not": { "any": excluded } }] }),
+            };
+
+            let group = &mut groups[target];
+            match item.get("position").and_then(Value::as_str) {
+                None => group.include.push(rule),
+                Some("before") => group.before.push(rule),
+                Some("after") => group.after.push(rule),
+                Some(position) => {
+                    self.warn(format!(
+                        "`pathGroups[{index}]`: unknown position `{position}`"
+                    ));
+                    group.include.push(rule);
+                }
+            }
+
+            converted = true;
+            positioned |= item.get("position").is_some();
+        }
+
+        let distinct = options.get("distinctGroup").and_then(Value::as_bool);
+        if positioned && distinct == Some(false) {
+            self.warn("`distinctGroup: false` is not supported, path groups are always separated");
+        }
+
+        converted
+    }
+
+    fn convert_newlines(&mut self, value: Option<&Value>) -> bool {
+        match value.and_then(Value::as_str) {
+            None => false,
+            Some("always") => true,
+            Some("never") => false,
+            Some("ignore") => {
+                self.warn("`newlines-between: ignore` is not supported, groups are not separated");
+                false
+            }
+            Some("always-and-inside-groups") => {
+                self.warn("`newlines-between: always-and-inside-groups` is not supported, groups are separated but blank lines inside groups are removed");
+                true
+            }
+            Some(value) => {
+                self.warn(format!("unknown `newlines-between` value `{value}`"));
+                false
+            }
+        }
+    }
+
+    fn convert_alphabetize(&mut self, value: Option<&Value>) -> &'static str {
+        let Some(value) = value else {
+            return "none";
+        };
+
+        let order = value
+            .get("order")
+            .and_then(Value::as_str)
+            .unwrap_or("ignore");
+        let case_insensitive = value.get("caseInsensitive").and_then(Value::as_bool);
+        let case_sensitive_order = match case_insensitive {
+            Some(true) => "caseInsensitive",
+            _ => "caseSensitive",
+        };
+
+        let kind_order = value.get("orderImportKind").and_then(Value::as_str);
+        if kind_order.is_some_and(|it| it != "ignore") {
+            self.warn("`alphabetize.orderImportKind` is not supported");
+        }
+
+        match order {
+            "ignore" => "none",
+            "asc" => case_sensitive_order,
+            "desc" => {
+                self.warn("`alphabetize.order: desc` is not supported, sorting in ascending order");
+                case_sensitive_order
+            }
+            _ => {
+                self.warn(format!("unknown `alphabetize.order` value `{order}`"));
+                "none"
+            }
+        }
+    }
+}
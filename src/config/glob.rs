@@ -0,0 +1,277 @@
+use anyhow::{anyhow, bail, Error, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A glob pattern with path-segment semantics, compiled into a regex.
+///
+/// - `*` matches any characters except `/`
+/// - `?` matches one character except `/`
+/// - `**` matches any number of path segments and must be a whole segment
+/// - `[abc]`, `[a-z]` and `[!abc]` match one character in (or not in) the set
+/// - `{a,b}` matches either of the alternatives
+/// - `\` escapes the next character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Glob {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let translated =
+            translate(pattern).map_err(|err| anyhow!("invalid glob `{pattern}`: {err}"))?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&translated)?,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+
+    /// Matches the trailing segments of a file path, so that `*.test.ts` matches the file name, and
+    /// `src/**/*.ts` matches the files under any `src` directory.
+    pub fn matches_file(&self, path: &str) -> bool {
+        let path = path.replace('\\', "/");
+        let mut rest = path.as_str();
+
+        loop {
+            if self.matches(rest) {
+                return true;
+            }
+
+            match rest.split_once('/') {
+                Some((_, next)) => rest = next,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for Glob {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<Glob> for String {
+    fn from(value: Glob) -> Self {
+        value.pattern
+    }
+}
+
+fn translate(pattern: &str) -> Result<String> {
+    if pattern.is_empty() {
+        bail!("empty pattern");
+    }
+
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut re = String::from("^");
+    let mut braces = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if i > 0 && chars[i - 1] != '/' {
+                    bail!("`**` must be a whole path segment");
+                }
+
+                match chars.get(i + 2) {
+                    None => {
+                        re.push_str(".*");
+                        i += 2;
+                    }
+                    Some('/') => {
+                        re.push_str("(?:[^/]*/)*");
+                        i += 3;
+                    }
+                    Some(_) => bail!("`**` must be a whole path segment"),
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => {
+                // A `]` right after the opening bracket is a literal
+                let start = i + 1;
+                let closing = chars
+                    .get(start + 1..)
+                    .and_then(|rest| rest.iter().position(|&c| c == ']'));
+                let Some(len) = closing else {
+                    bail!("unclosed `[`");
+                };
+                let end = start + 1 + len;
+
+                re.push('[');
+                let mut class = &chars[start..end];
+                if let Some(('!' | '^', rest)) = class.split_first() {
+                    re.push_str("^/");
+                    class = rest;
+                }
+                for &c in class {
+                    if matches!(c, '\\' | '[' | ']' | '&' | '~' | '^') {
+                        re.push('\\');
+                    }
+                    re.push(c);
+                }
+                re.push(']');
+
+                i = end;
+            }
+            '{' => {
+                braces += 1;
+                re.push_str("(?:");
+            }
+            ',' if braces > 0 => re.push('|'),
+            '}' if braces > 0 => {
+                braces -= 1;
+                re.push(')');
+            }
+            '}' => bail!("unmatched `}}`"),
+            '\\' => {
+                i += 1;
+                let Some(&c) = chars.get(i) else {
+                    bail!("trailing `\\`");
+                };
+                re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])));
+            }
+            c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+
+        i += 1;
+    }
+
+    if braces > 0 {
+        bail!("unclosed `{{`");
+    }
+
+    re.push('$');
+    Ok(re)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str) -> Glob {
+        Glob::new(pattern).unwrap()
+    }
+
+    fn error(pattern: &str) -> String {
+        Glob::new(pattern).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn match_segments() {
+        let it = glob("*.ts");
+        assert!(it.matches("a.ts"));
+        assert!(!it.matches("a/b.ts"));
+
+        let it = glob("?.ts");
+        assert!(it.matches("a.ts"));
+        assert!(!it.matches("ab.ts"));
+        assert!(!it.matches("/.ts"));
+    }
+
+    #[test]
+    fn match_globstar() {
+        let it = glob("**/*.css");
+        assert!(it.matches("a.css"));
+        assert!(it.matches("./styles/a.css"));
+        assert!(!it.matches("a.scss"));
+
+        let it = glob("@acme/**");
+        assert!(it.matches("@acme/ui"));
+        assert!(it.matches("@acme/ui/button"));
+        assert!(!it.matches("@acme"));
+        assert!(!it.matches("@acmex/ui"));
+
+        let it = glob("src/**/index.ts");
+        assert!(it.matches("src/index.ts"));
+        assert!(it.matches("src/a/b/index.ts"));
+        assert!(!it.matches("src/a/myindex.ts"));
+    }
+
+    #[test]
+    fn match_classes() {
+        let it = glob("[abc]");
+        assert!(it.matches("b"));
+        assert!(!it.matches("d"));
+
+        let it = glob("[a-c]x");
+        assert!(it.matches("bx"));
+        assert!(!it.matches("dx"));
+
+        for pattern in ["[!x]", "[^x]"] {
+            let it = glob(pattern);
+            assert!(it.matches("a"));
+            assert!(!it.matches("x"));
+            assert!(!it.matches("/"));
+        }
+
+        let it = glob("[]]");
+        assert!(it.matches("]"));
+    }
+
+    #[test]
+    fn match_braces() {
+        let it = glob("*.{ts,tsx}");
+        assert!(it.matches("a.ts"));
+        assert!(it.matches("a.tsx"));
+        assert!(!it.matches("a.js"));
+
+        let it = glob("{a,{b,c}d}");
+        assert!(it.matches("a"));
+        assert!(it.matches("cd"));
+        assert!(!it.matches("c"));
+
+        // A comma outside braces is a literal
+        assert!(glob("a,b").matches("a,b"));
+    }
+
+    #[test]
+    fn match_escapes() {
+        let it = glob(r"\*");
+        assert!(it.matches("*"));
+        assert!(!it.matches("a"));
+
+        assert!(glob(r"\{a\}").matches("{a}"));
+        assert!(glob("a.b").matches("a.b"));
+        assert!(!glob("a.b").matches("axb"));
+    }
+
+    #[test]
+    fn match_file_paths() {
+        let it = glob("src/**/*.ts");
+        assert!(it.matches_file("/repo/src/a.ts"));
+        assert!(it.matches_file(r"C:\repo\src\a\b.ts"));
+        assert!(!it.matches_file("/repo/lib/a.ts"));
+    }
+
+    #[test]
+    fn reject_malformed() {
+        assert_eq!(error(""), "invalid glob ``: empty pattern");
+        assert_eq!(
+            error("a**"),
+            "invalid glob `a**`: `**` must be a whole path segment"
+        );
+        assert_eq!(
+            error("**a"),
+            "invalid glob `**a`: `**` must be a whole path segment"
+        );
+        assert_eq!(error("[abc"), "invalid glob `[abc`: unclosed `[`");
+        assert_eq!(error("{a,b"), "invalid glob `{a,b`: unclosed `{`");
+        assert_eq!(error("a}"), "invalid glob `a}`: unmatched `}`");
+        assert_eq!(error(r"a\"), r"invalid glob `a\`: trailing `\`");
+    }
+}
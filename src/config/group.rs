@@ -1,12 +1,103 @@
 use serde::{Deserialize, Serialize};
 
-use super::RuleSet;
+use anyhow::Error;
+
+use super::{RawRule, Rule, RuleSet, SplitBy};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportGroup {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     #[serde(default)]
     pub include: RuleSet,
     #[serde(default)]
     pub exclude: RuleSet,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<OrderEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_by: Option<SplitBy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<SortOrder>,
+}
+
+impl ImportGroup {
+    /// The name of the group, or its position in the config if it has no name.
+    pub fn label(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => format!("group `{name}`"),
+            None => format!("groups[{index}]"),
+        }
+    }
+
+    /// Whether the imports matching no group should be placed in this group.
+    pub fn includes_unmatched(&self) -> bool {
+        self.include
+            .iter()
+            .any(|rule| matches!(rule, Rule::Unmatched))
+    }
+}
+
+/// An entry of the `order` of a group, which pins the matching imports before or after the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawRule", into = "RawRule")]
+pub enum OrderEntry {
+    /// The `"..."` placeholder for the imports not pinned.
+    Rest,
+    Rule(Rule),
+}
+
+impl TryFrom<RawRule> for OrderEntry {
+    type Error = Error;
+
+    fn try_from(value: RawRule) -> Result<Self, Self::Error> {
+        match value {
+            RawRule::String(value) if value == "..." => Ok(OrderEntry::Rest),
+            value => Ok(OrderEntry::Rule(Rule::try_from(value)?)),
+        }
+    }
+}
+
+impl From<OrderEntry> for RawRule {
+    fn from(value: OrderEntry) -> Self {
+        match value {
+            OrderEntry::Rest => RawRule::String("...".to_string()),
+            OrderEntry::Rule(rule) => rule.into(),
+        }
+    }
+}
+
+/// What to do with the imports matching no group when there is no `<unmatched>` group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnmatchedImports {
+    /// Place them in a trailing group.
+    #[default]
+    Last,
+    /// Fail to format the file.
+    Error,
+}
+
+/// How to choose the group of an import that could be matched by multiple groups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchStrategy {
+    /// The first group in the config.
+    #[default]
+    First,
+    /// The group with the most specific matching rule, or the first one if tied.
+    Specificity,
+}
+
+/// How to sort the imports in a group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    /// Alphabetically, ignoring case unless the modules differ only in case.
+    #[default]
+    CaseInsensitive,
+    /// Alphabetically, with uppercase letters before lowercase letters.
+    CaseSensitive,
+    /// Keep the original order.
+    None,
 }
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// An import map, or a deno.json with the `imports` field. Fields other than `imports` are ignored,
+/// so the whole deno.json can be pasted in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMap {
+    #[serde(default)]
+    pub imports: BTreeMap<String, String>,
+}
+
+impl ImportMap {
+    /// Finds the target of the entry mapping the specifier, either the exact key or the longest key
+    /// ending with `/` that prefixes the specifier.
+    pub fn resolve(&self, specifier: &str) -> Option<&str> {
+        if let Some(target) = self.imports.get(specifier) {
+            return Some(target);
+        }
+
+        self.imports
+            .iter()
+            .filter(|(key, _)| key.ends_with('/') && specifier.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, target)| target.as_str())
+    }
+
+    /// Whether the specifier is mapped to a package or a remote module, rather than to the files of
+    /// the project.
+    pub fn is_external(&self, specifier: &str) -> Option<bool> {
+        let target = self.resolve(specifier)?;
+
+        Some(
+            ["jsr:", "npm:", "node:", "http://", "https://"]
+                .iter()
+                .any(|it| target.starts_with(it)),
+        )
+    }
+}
@@ -0,0 +1,495 @@
+use dprint_core::configuration::ConfigurationDiagnostic;
+
+use crate::re;
+
+use super::{get_default_aliases, Configuration, CustomRule, ImportGroup, MatchStrategy, Rule};
+
+/// Finds the groups and rules which never take effect. Such configs still resolve, and the problems
+/// are returned as warnings, which the plugin reports only with `strict`.
+pub fn lint_config(config: &Configuration) -> Vec<ConfigurationDiagnostic> {
+    let mut linter = Linter {
+        config,
+        warnings: Vec::new(),
+    };
+
+    for (index, group) in config.groups.iter().enumerate() {
+        linter.lint_duplicates(index, group);
+        linter.lint_covered(index, group);
+        linter.lint_exclude(index, group);
+        linter.lint_alias_overlaps(index, group);
+    }
+
+    linter.lint_unused_aliases();
+    linter.lint_alias_rewrite();
+    linter.warnings
+}
+
+struct Linter<'a> {
+    config: &'a Configuration,
+    warnings: Vec<ConfigurationDiagnostic>,
+}
+
+impl Linter<'_> {
+    fn warn(&mut self, property_name: String, message: String) {
+        self.warnings.push(ConfigurationDiagnostic {
+            property_name,
+            message,
+        });
+    }
+
+    fn lint_duplicates(&mut self, index: usize, group: &ImportGroup) {
+        for (name, rules) in [("include", &group.include), ("exclude", &group.exclude)] {
+            let rules: Vec<_> = rules.iter().map(display).collect();
+
+            for (position, rule) in rules.iter().enumerate() {
+                if rules[..position].contains(rule) {
+                    self.warn(
+                        format!("groups[{index}].{name}[{position}]"),
+                        format!(
+                            "{}: {rule} is listed more than once in `{name}`",
+                            group.label(index),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Finds the included rules whose imports are all taken by the earlier groups.
+    fn lint_covered(&mut self, index: usize, group: &ImportGroup) {
+        if group.includes_unmatched() {
+            return;
+        }
+
+        let config = self.config;
+        let earlier = &config.groups[..index];
+        let covered: Vec<_> = group
+            .include
+            .iter()
+            .enumerate()
+            .filter_map(|(position, rule)| {
+                let first = earlier.iter().position(|it| self.takes_all(it, rule))?;
+                Some((position, rule, first))
+            })
+            .collect();
+
+        if covered.is_empty() {
+            return;
+        }
+
+        if covered.len() == group.include.iter().count() {
+            let mut labels: Vec<_> = covered
+                .iter()
+                .map(|&(_, _, first)| earlier[first].label(first))
+                .collect();
+            labels.dedup();
+
+            self.warn(
+                format!("groups[{index}]"),
+                format!(
+                    "{} is unreachable, all its imports are matched by {}",
+                    group.label(index),
+                    labels.join(" and "),
+                ),
+            );
+            return;
+        }
+
+        for (position, rule, first) in covered {
+            self.warn(
+                format!("groups[{index}].include[{position}]"),
+                format!(
+                    "{}: {} is already matched by {}",
+                    group.label(index),
+                    display(rule),
+                    earlier[first].label(first),
+                ),
+            );
+        }
+    }
+
+    /// Finds the excluded rules which match none of the included imports.
+    fn lint_exclude(&mut self, index: usize, group: &ImportGroup) {
+        if group.includes_unmatched() {
+            return;
+        }
+
+        for (position, rule) in group.exclude.iter().enumerate() {
+            if group.include.iter().all(|it| self.disjoint(rule, it)) {
+                self.warn(
+                    format!("groups[{index}].exclude[{position}]"),
+                    format!(
+                        "{}: {} never matches the imports included by the group",
+                        group.label(index),
+                        display(rule),
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Finds the custom rules which may match the imports of an alias, which are taken by an
+    /// earlier group with <alias>. Custom rules in the groups before it carve imports out of it.
+    fn lint_alias_overlaps(&mut self, index: usize, group: &ImportGroup) {
+        // A custom rule is more specific than <alias>, so it wins wherever the groups are
+        if self.config.match_strategy == MatchStrategy::Specificity {
+            return;
+        }
+
+        let config = self.config;
+        let earlier = &config.groups[..index];
+        let Some(first) = earlier
+            .iter()
+            .position(|it| self.takes_all(it, &Rule::Alias))
+        else {
+            return;
+        };
+
+        let aliases = &config.aliases;
+        let mut customs = Vec::new();
+        for rule in group.include.iter() {
+            collect_customs(rule, &mut customs);
+        }
+
+        for custom in customs {
+            if aliases.iter().any(|it| same(it, custom)) {
+                continue;
+            }
+
+            let prefixes = literal_prefixes(custom);
+            let alias = aliases.iter().find(|alias| {
+                literal_prefixes(alias).iter().any(|a| {
+                    prefixes
+                        .iter()
+                        .any(|b| a.starts_with(b.as_str()) || b.starts_with(a.as_str()))
+                })
+            });
+
+            if let Some(alias) = alias {
+                self.warn(
+                    format!("groups[{index}]"),
+                    format!(
+                        "{}: {} overlaps the alias {}, whose imports are taken by {}",
+                        group.label(index),
+                        display(custom),
+                        display(alias),
+                        earlier[first].label(first),
+                    ),
+                );
+            }
+        }
+    }
+
+    fn lint_unused_aliases(&mut self) {
+        let config = self.config;
+
+        // The default aliases are not worth a warning if the groups do not use them
+        if same(&config.aliases, &get_default_aliases()) {
+            return;
+        }
+
+        let includes = || config.groups.iter().flat_map(|it| it.include.iter());
+        if includes().any(|rule| rule.contains(&|it| matches!(it, Rule::Alias))) {
+            return;
+        }
+
+        for (index, alias) in config.aliases.iter().enumerate() {
+            let included = includes().any(|rule| {
+                rule.contains(&|it| matches!(it, Rule::Custom(custom) if same(custom, alias)))
+            });
+
+            if !included {
+                self.warn(
+                    format!("aliases[{index}]"),
+                    format!(
+                        "the alias {} is not included by any group, so its imports are unmatched",
+                        display(alias),
+                    ),
+                );
+            }
+        }
+    }
+
+    fn lint_alias_rewrite(&mut self) {
+        let config = self.config;
+        if !config.alias_rewrite.is_enabled() {
+            return;
+        }
+
+        let has_directories = config
+            .alias_paths
+            .as_ref()
+            .is_some_and(|it| it.directories().next().is_some());
+
+        if !has_directories {
+            self.warn(
+                "aliasRewrite".to_string(),
+                "no alias in `aliasPaths` maps to a directory, so nothing is rewritten".to_string(),
+            );
+        }
+    }
+
+    /// Whether the group takes every import matching the rule before the later groups do.
+    fn takes_all(&self, group: &ImportGroup, rule: &Rule) -> bool {
+        if !group.exclude.is_empty() {
+            return false;
+        }
+
+        group
+            .include
+            .iter()
+            .any(|it| match self.config.match_strategy {
+                MatchStrategy::First => self.covers(it, rule),
+                // A more specific rule in a later group still wins
+                MatchStrategy::Specificity => {
+                    it.specificity() >= rule.specificity()
+                        && !matches!(rule, Rule::All(_) | Rule::Any(_))
+                        && self.covers(it, rule)
+                }
+            })
+    }
+
+    /// Whether every import matching `rule` also matches `broad`.
+    fn covers(&self, broad: &Rule, rule: &Rule) -> bool {
+        if matches!(broad, Rule::Unmatched) || matches!(rule, Rule::Unmatched) {
+            return false;
+        }
+
+        if same(broad, rule) {
+            return true;
+        }
+
+        match rule {
+            Rule::All(rules) => return rules.iter().any(|it| self.covers(broad, it)),
+            Rule::Any(rules) => return rules.iter().all(|it| self.covers(broad, it)),
+            _ => {}
+        }
+
+        match (broad, rule) {
+            (Rule::Any(rules), _) => rules.iter().any(|it| self.covers(it, rule)),
+            (Rule::Relative, Rule::Parent | Rule::Sibling | Rule::Index) => true,
+            (Rule::Relative, Rule::Custom(CustomRule::Prefix(prefix))) => prefix.starts_with('.'),
+            (Rule::Builtin, Rule::Custom(CustomRule::Prefix(prefix))) => {
+                prefix.starts_with("node:")
+            }
+            (Rule::Alias, Rule::Custom(custom)) => {
+                self.config.aliases.iter().any(|it| same(it, custom))
+            }
+            (
+                Rule::Npm,
+                Rule::Dependency
+                | Rule::DevDependency
+                | Rule::PeerDependency
+                | Rule::SelfPackage
+                | Rule::Undeclared,
+            ) => true,
+            (Rule::Npm, Rule::Custom(CustomRule::Packages(packages))) => {
+                packages.iter().all(|name| {
+                    re!(r"^@?[0-9A-Za-z\-]").is_match(name)
+                        && !self.config.aliases.iter().any(|it| it.matches(name))
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether no import matches both rules.
+    fn disjoint(&self, a: &Rule, b: &Rule) -> bool {
+        match (a, b) {
+            (Rule::Unmatched, _) | (_, Rule::Unmatched) => false,
+            (Rule::All(rules), _) => rules.iter().any(|it| self.disjoint(it, b)),
+            (_, Rule::All(rules)) => rules.iter().any(|it| self.disjoint(a, it)),
+            (Rule::Any(rules), _) => rules.iter().all(|it| self.disjoint(it, b)),
+            (_, Rule::Any(rules)) => rules.iter().all(|it| self.disjoint(a, it)),
+            (Rule::Not(rule), _) => self.covers(rule, b),
+            (_, Rule::Not(rule)) => self.covers(rule, a),
+            _ => disjoint_leaves(a, b) || disjoint_leaves(b, a),
+        }
+    }
+}
+
+fn disjoint_leaves(a: &Rule, b: &Rule) -> bool {
+    use CustomRule::{Packages, Prefix};
+
+    let is_shape = |rule: &Rule| {
+        matches!(
+            rule,
+            Rule::Effect | Rule::Default | Rule::Namespace | Rule::Named | Rule::Mixed
+        )
+    };
+
+    // An import has exactly one shape of bindings
+    if is_shape(a) && is_shape(b) {
+        return !same(a, b);
+    }
+
+    // A relative import is exactly one of them, and is disjoint with others like <relative> is
+    let is_relative = |rule: &Rule| matches!(rule, Rule::Parent | Rule::Sibling | Rule::Index);
+    if is_relative(a) && is_relative(b) {
+        return !same(a, b);
+    }
+
+    let a = if is_relative(a) { &Rule::Relative } else { a };
+    let b = if is_relative(b) { &Rule::Relative } else { b };
+
+    match (a, b) {
+        (Rule::Relative, Rule::Npm | Rule::Builtin) => true,
+        (Rule::Subpath, Rule::Relative | Rule::Builtin) => true,
+        (
+            Rule::Relative | Rule::Alias,
+            Rule::Dependency
+            | Rule::DevDependency
+            | Rule::PeerDependency
+            | Rule::SelfPackage
+            | Rule::Undeclared,
+        ) => true,
+        (Rule::Undeclared, Rule::Dependency | Rule::DevDependency | Rule::PeerDependency) => true,
+        (Rule::Undeclared, Rule::SelfPackage) => true,
+        // `import type "x"` is not valid
+        (Rule::Type, Rule::Effect) => true,
+        (Rule::Alias, Rule::Npm) => true,
+        (Rule::Relative, Rule::Custom(Packages(_))) => true,
+        (Rule::Relative, Rule::Custom(Prefix(prefix))) => !prefix.starts_with('.'),
+        (Rule::Custom(Prefix(prefix)), Rule::Npm | Rule::Builtin | Rule::Custom(Packages(_))) => {
+            prefix.starts_with('.')
+        }
+        (Rule::Custom(Prefix(a)), Rule::Custom(Prefix(b))) => {
+            !a.starts_with(b.as_str()) && !b.starts_with(a.as_str())
+        }
+        _ => false,
+    }
+}
+
+fn collect_customs<'a>(rule: &'a Rule, customs: &mut Vec<&'a CustomRule>) {
+    match rule {
+        Rule::Custom(custom) => customs.push(custom),
+        Rule::All(rules) | Rule::Any(rules) => {
+            for rule in rules.iter() {
+                collect_customs(rule, customs);
+            }
+        }
+        Rule::Not(rule) => collect_customs(rule, customs),
+        _ => {}
+    }
+}
+
+/// The literal text every module matching the rule starts with, or nothing if unknown.
+fn literal_prefixes(rule: &CustomRule) -> Vec<String> {
+    let prefix = match rule {
+        CustomRule::Regex { regex, flags } if flags.is_empty() => regex_prefix(regex.as_str()),
+        CustomRule::Regex { .. } => None,
+        CustomRule::Glob(glob) => glob_prefix(glob.pattern()),
+        CustomRule::Packages(packages) => return packages.clone(),
+        CustomRule::Prefix(prefix) => Some(prefix.clone()),
+    };
+
+    prefix.into_iter().collect()
+}
+
+fn regex_prefix(pattern: &str) -> Option<String> {
+    let rest = pattern.strip_prefix('^')?;
+    if rest.contains('|') {
+        return None;
+    }
+
+    let mut prefix = String::new();
+    let mut chars = rest.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let literal = match c {
+            '\\' => match chars.next() {
+                Some(c) if !c.is_alphanumeric() => c,
+                _ => break,
+            },
+            '.' | '[' | '(' | ')' | '$' | '*' | '+' | '?' | '{' => break,
+            c => c,
+        };
+
+        // A quantifier makes the character optional or repeated
+        match chars.peek() {
+            Some('?' | '*' | '{') => break,
+            Some('+') => {
+                prefix.push(literal);
+                break;
+            }
+            _ => prefix.push(literal),
+        }
+    }
+
+    (!prefix.is_empty()).then_some(prefix)
+}
+
+fn glob_prefix(pattern: &str) -> Option<String> {
+    let mut prefix = String::new();
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c) => prefix.push(c),
+                None => break,
+            },
+            '*' | '?' | '[' | '{' => break,
+            c => prefix.push(c),
+        }
+    }
+
+    (!prefix.is_empty()).then_some(prefix)
+}
+
+fn display<T: serde::Serialize>(rule: &T) -> String {
+    serde_json::to_string(rule).unwrap()
+}
+
+fn same<T: serde::Serialize>(a: &T, b: &T) -> bool {
+    display(a) == display(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use dprint_core::configuration::ConfigKeyMap;
+    use serde_json::json;
+
+    use crate::config::resolve_config;
+
+    fn lint(config: serde_json::Value) -> Vec<String> {
+        let mut raw: ConfigKeyMap = serde_json::from_value(config).unwrap();
+        let mut warnings = vec![];
+        resolve_config(&mut raw, &mut warnings).unwrap();
+        warnings.into_iter().map(|it| it.message).collect()
+    }
+
+    #[test]
+    fn carve_out_of_alias() {
+        let config = json!({
+            "aliases": ["^@/"],
+            "groups": [
+                { "include": ["^@/components"] },
+                { "include": ["<alias>"] },
+                { "include": ["<unmatched>"] },
+            ],
+        });
+        assert!(lint(config).is_empty());
+    }
+
+    #[test]
+    fn overlap_alias_after_it() {
+        let config = json!({
+            "aliases": ["^@/"],
+            "groups": [
+                { "include": ["<alias>"] },
+                { "include": ["^@/components"] },
+                { "include": ["<unmatched>"] },
+            ],
+        });
+        let message = r#"groups[1]: "^@/components" overlaps the alias "^@/", whose imports are "#;
+        assert_eq!(
+            lint(config.clone()),
+            [format!("{message}taken by groups[0]")]
+        );
+
+        let mut config = config;
+        config["matchStrategy"] = json!("specificity");
+        assert!(lint(config).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+use dprint_core::configuration::{ConfigKeyMap, ConfigKeyValue};
+use dprint_core::plugins::{ConfigChange, ConfigChangeKind, ConfigChangePathItem};
+
+use super::{convert_import_order, MigrateFrom};
+
+type Version = (u32, u32, u32);
+
+/// A step of updating the configs written for older versions of the plugin.
+struct Migration {
+    /// The last version whose configs need this step, or `None` if every config needs it.
+    until: Option<Version>,
+    migrate: fn(&ConfigKeyMap) -> Vec<ConfigChange>,
+}
+
+const MIGRATIONS: [Migration; 2] = [
+    Migration {
+        until: Some((0, 1, 6)),
+        migrate: quote_glob_prefixed_regexes,
+    },
+    Migration {
+        until: None,
+        migrate: expand_migrate_from,
+    },
+];
+
+/// Returns the changes updating a config written for `old_version` of the plugin.
+pub fn migrate_config(config: &ConfigKeyMap, old_version: Option<&str>) -> Vec<ConfigChange> {
+    let old_version = old_version.and_then(parse_version);
+
+    MIGRATIONS
+        .iter()
+        .filter(|step| match (step.until, old_version) {
+            (None, _) => true,
+            (Some(until), Some(old)) => old <= until,
+            // Older dprint does not send the version, and guessing could break the configs
+            (Some(_), None) => false,
+        })
+        .flat_map(|step| (step.migrate)(config))
+        .collect()
+}
+
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(|it| it.parse().ok());
+
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+fn key(name: &str) -> ConfigChangePathItem {
+    ConfigChangePathItem::String(name.to_string())
+}
+
+/// Up to 0.1.6, a rule starting with `glob:` was a regex, which is a glob rule in later versions.
+fn quote_glob_prefixed_regexes(config: &ConfigKeyMap) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    quote_glob_options(&mut changes, &[], config);
+
+    if let Some(ConfigKeyValue::Array(overrides)) = config.get("overrides") {
+        for (index, item) in overrides.iter().enumerate() {
+            let ConfigKeyValue::Object(item) = item else {
+                continue;
+            };
+
+            let path = [key("overrides"), ConfigChangePathItem::Number(index)];
+            quote_glob_options(&mut changes, &path, item);
+        }
+    }
+
+    changes
+}
+
+/// Quotes the rules in `aliases` and `groups` of the config or an override.
+fn quote_glob_options(
+    changes: &mut Vec<ConfigChange>,
+    path: &[ConfigChangePathItem],
+    options: &ConfigKeyMap,
+) {
+    quote_glob_rules(
+        changes,
+        &join(path, [key("aliases")]),
+        options.get("aliases"),
+    );
+
+    let Some(ConfigKeyValue::Array(groups)) = options.get("groups") else {
+        return;
+    };
+
+    for (index, group) in groups.iter().enumerate() {
+        let ConfigKeyValue::Object(group) = group else {
+            continue;
+        };
+
+        for name in ["include", "exclude", "order"] {
+            let path = join(
+                path,
+                [
+                    key("groups"),
+                    ConfigChangePathItem::Number(index),
+                    key(name),
+                ],
+            );
+            quote_glob_rules(changes, &path, group.get(name));
+        }
+    }
+}
+
+fn quote_glob_rules(
+    changes: &mut Vec<ConfigChange>,
+    path: &[ConfigChangePathItem],
+    rules: Option<&ConfigKeyValue>,
+) {
+    let Some(ConfigKeyValue::Array(rules)) = rules else {
+        return;
+    };
+
+    for (index, rule) in rules.iter().enumerate() {
+        let path = join(path, [ConfigChangePathItem::Number(index)]);
+        quote_glob_rule(changes, &path, rule);
+    }
+}
+
+fn quote_glob_rule(
+    changes: &mut Vec<ConfigChange>,
+    path: &[ConfigChangePathItem],
+    rule: &ConfigKeyValue,
+) {
+    match rule {
+        ConfigKeyValue::String(rule) if rule.starts_with("glob:") => {
+            let object =
+                ConfigKeyMap::from([("regex".to_string(), ConfigKeyValue::String(rule.clone()))]);
+
+            changes.push(ConfigChange {
+                path: path.to_vec(),
+                kind: ConfigChangeKind::Set(ConfigKeyValue::Object(object)),
+            });
+        }
+        // Rules combined with `all`, `any` and `not`
+        ConfigKeyValue::Object(rule) => {
+            for name in ["all", "any"] {
+                quote_glob_rules(changes, &join(path, [key(name)]), rule.get(name));
+            }
+
+            if let Some(not) = rule.get("not") {
+                quote_glob_rule(changes, &join(path, [key("not")]), not);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join<const N: usize>(
+    path: &[ConfigChangePathItem],
+    items: [ConfigChangePathItem; N],
+) -> Vec<ConfigChangePathItem> {
+    let mut path = path.to_vec();
+    path.extend(items);
+    path
+}
+
+/// Replaces `migrateFrom` with the converted options, keeping the options set in the config.
+fn expand_migrate_from(config: &ConfigKeyMap) -> Vec<ConfigChange> {
+    let Some(value) = config.get("migrateFrom") else {
+        return vec![];
+    };
+
+    // Invalid values are left as is, and reported when resolving the config
+    let json = serde_json::to_value(value).unwrap();
+    let Ok(migrate_from) = serde_json::from_value::<MigrateFrom>(json) else {
+        return vec![];
+    };
+
+    let mut changes = Vec::new();
+
+    if let Some(options) = migrate_from.import_order {
+        // The conversion warnings are reported when resolving the config with `migrateFrom`
+        let conversion = convert_import_order(&options);
+        for (name, value) in conversion.options {
+            if config.contains_key(&name) {
+                continue;
+            }
+
+            changes.push(ConfigChange {
+                path: vec![key(&name)],
+                kind: ConfigChangeKind::Add(serde_json::from_value(value).unwrap()),
+            });
+        }
+    }
+
+    changes.push(ConfigChange {
+        path: vec![key("migrateFrom")],
+        kind: ConfigChangeKind::Remove,
+    });
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn migrate(config: Value, old_version: Option<&str>) -> Value {
+        let config: ConfigKeyMap = serde_json::from_value(config).unwrap();
+        serde_json::to_value(migrate_config(&config, old_version)).unwrap()
+    }
+
+    #[test]
+    fn quote_glob_prefixed_regexes() {
+        let config = json!({
+            "aliases": ["glob:^@app/", "^~/"],
+            "groups": [
+                { "include": ["glob:^a"], "exclude": ["glob:^b"], "order": ["glob:^c", "..."] },
+                { "include": [{ "all": ["<npm>", { "not": "glob:^d" }] }, { "any": ["glob:^e"] }] },
+            ],
+            "overrides": [
+                {
+                    "files": "**/*.ts",
+                    "aliases": ["glob:^f"],
+                    "groups": [{ "include": ["glob:^g"] }],
+                },
+            ],
+        });
+
+        let set = |path: Value, pattern: &str| {
+            let value = json!({ "regex": pattern });
+            json!({ "path": path, "kind": "Set", "value": value })
+        };
+
+        let changes = json!([
+            set(json!(["aliases", 0]), "glob:^@app/"),
+            set(json!(["groups", 0, "include", 0]), "glob:^a"),
+            set(json!(["groups", 0, "exclude", 0]), "glob:^b"),
+            set(json!(["groups", 0, "order", 0]), "glob:^c"),
+            set(
+                json!(["groups", 1, "include", 0, "all", 1, "not"]),
+                "glob:^d"
+            ),
+            set(json!(["groups", 1, "include", 1, "any", 0]), "glob:^e"),
+            set(json!(["overrides", 0, "aliases", 0]), "glob:^f"),
+            set(
+                json!(["overrides", 0, "groups", 0, "include", 0]),
+                "glob:^g"
+            ),
+        ]);
+
+        assert_eq!(migrate(config.clone(), Some("0.1.6")), changes);
+        assert_eq!(migrate(config.clone(), Some("0.1.7")), json!([]));
+        assert_eq!(migrate(config, None), json!([]));
+    }
+
+    #[test]
+    fn expand_migrate_from() {
+        let config = json!({
+            "sortOrder": "caseSensitive",
+            "migrateFrom": { "importOrder": { "alphabetize": { "order": "asc" } } },
+        });
+
+        // Options set in the config are kept
+        let changes = json!([
+            {
+                "path": ["groups"],
+                "kind": "Add",
+                "value": [
+                    { "include": ["<builtin>"] },
+                    { "include": ["<npm>"] },
+                    { "include": ["<parent>"] },
+                    { "include": ["<sibling>"] },
+                    { "include": ["<index>"] },
+                ],
+            },
+            { "path": ["separateGroups"], "kind": "Add", "value": false },
+            { "path": ["migrateFrom"], "kind": "Remove" },
+        ]);
+
+        assert_eq!(migrate(config, None), changes);
+    }
+}
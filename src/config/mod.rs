@@ -1,69 +1,281 @@
+use std::borrow::Cow;
+use std::path::Path;
+
 use dprint_core::configuration::{ConfigKeyMap, ConfigurationDiagnostic};
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+mod alias_paths;
 mod custom;
+mod eslint;
+mod glob;
 mod group;
+mod import_map;
+mod lint;
+mod migrate;
+mod normalize;
+mod overrides;
+mod package;
+mod preset;
+mod raw;
 mod rule;
 mod set;
+mod split;
 
+pub use alias_paths::*;
 pub use custom::*;
+pub use eslint::*;
+pub use glob::*;
 pub use group::*;
+pub use import_map::*;
+pub use lint::*;
+pub use migrate::*;
+pub use normalize::*;
+pub use overrides::*;
+pub use package::*;
+pub use preset::*;
+pub use raw::*;
 pub use rule::*;
 pub use set::*;
+pub use split::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
     #[serde(default = "get_default_aliases")]
     pub aliases: Vec<CustomRule>,
     #[serde(default = "get_default_groups")]
     pub groups: Vec<ImportGroup>,
+    #[serde(default = "get_default_asset_extensions")]
+    pub asset_extensions: Vec<String>,
+    #[serde(default)]
+    pub unmatched: UnmatchedImports,
+    #[serde(default)]
+    pub match_strategy: MatchStrategy,
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    #[serde(default = "get_default_separate_groups")]
+    pub separate_groups: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_json: Option<PackageJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_map: Option<ImportMap>,
+    #[serde(default)]
+    pub remove_unused: bool,
+    #[serde(default)]
+    pub keep_side_effects: bool,
+    #[serde(default = "get_default_keep_unused")]
+    pub keep_unused: Vec<String>,
+    #[serde(default)]
+    pub normalize: Normalize,
+    #[serde(default)]
+    pub type_import_style: TypeImportStyle,
+    #[serde(default)]
+    pub relative_extensions: RelativeExtensions,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_paths: Option<AliasPaths>,
+    #[serde(default)]
+    pub alias_rewrite: AliasRewrite,
+    #[serde(default)]
+    pub builtin_prefix: BuiltinPrefix,
+    #[serde(default)]
+    pub strict: bool,
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
 }
 
 impl Configuration {
     pub fn empty() -> Self {
         Self {
+            preset: None,
             aliases: vec![],
             groups: vec![],
+            asset_extensions: vec![],
+            unmatched: UnmatchedImports::default(),
+            match_strategy: MatchStrategy::default(),
+            sort_order: SortOrder::default(),
+            separate_groups: get_default_separate_groups(),
+            package_json: None,
+            import_map: None,
+            remove_unused: false,
+            keep_side_effects: false,
+            keep_unused: get_default_keep_unused(),
+            normalize: Normalize::default(),
+            type_import_style: TypeImportStyle::default(),
+            relative_extensions: RelativeExtensions::default(),
+            alias_paths: None,
+            alias_rewrite: AliasRewrite::default(),
+            builtin_prefix: BuiltinPrefix::default(),
+            strict: false,
+            overrides: vec![],
         }
     }
+
+    /// Applies the overrides matching the file, in the order they appear in the config.
+    pub fn resolve_for(&self, path: &Path) -> Cow<'_, Self> {
+        let path = path.to_string_lossy();
+
+        let mut config = Cow::Borrowed(self);
+        for item in &self.overrides {
+            if item.files.matches_file(&path) {
+                item.apply(config.to_mut());
+            }
+        }
+
+        config
+    }
 }
 
 fn get_default_aliases() -> Vec<CustomRule> {
-    vec![CustomRule(Regex::new("^[@~]/").unwrap())]
+    vec![CustomRule::regex("^[@~]/", "").unwrap()]
+}
+
+fn get_default_asset_extensions() -> Vec<String> {
+    [
+        "css", "scss", "sass", "less", "styl", "pcss", "svg", "png", "jpg", "jpeg", "gif", "webp",
+        "avif", "ico", "bmp", "woff", "woff2", "ttf", "otf", "eot", "mp3", "mp4", "webm", "ogg",
+        "wav", "json", "wasm", "txt", "md",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn get_default_separate_groups() -> bool {
+    true
+}
+
+fn get_default_keep_unused() -> Vec<String> {
+    // The classic JSX runtime references `React` implicitly
+    vec!["React".to_string()]
 }
 
 fn get_default_groups() -> Vec<ImportGroup> {
     vec![
         ImportGroup {
+            name: None,
             include: RuleSet::single(Rule::Effect),
             exclude: RuleSet::default(),
+            order: vec![],
+            split_by: None,
+            sort_order: None,
         },
         ImportGroup {
+            name: None,
             include: RuleSet::single(Rule::Builtin),
             exclude: RuleSet::default(),
+            order: vec![],
+            split_by: None,
+            sort_order: None,
         },
         ImportGroup {
+            name: None,
             include: RuleSet::single(Rule::Npm),
             exclude: RuleSet::default(),
+            order: vec![],
+            split_by: None,
+            sort_order: None,
         },
         ImportGroup {
+            name: None,
             include: RuleSet::single(Rule::Alias),
             exclude: RuleSet::default(),
+            order: vec![],
+            split_by: None,
+            sort_order: None,
         },
         ImportGroup {
+            name: None,
             include: RuleSet::single(Rule::Relative),
             exclude: RuleSet::default(),
+            order: vec![],
+            split_by: None,
+            sort_order: None,
         },
     ]
 }
 
-pub fn resolve_config(raw: &mut ConfigKeyMap) -> Result<Configuration, ConfigurationDiagnostic> {
-    let json = serde_json::to_value(&raw).unwrap();
+/// Configs of other tools to migrate from, converted into options with lower precedence than the
+/// explicit ones.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MigrateFrom {
+    import_order: Option<serde_json::Value>,
+}
 
+pub fn resolve_config(
+    raw: &mut ConfigKeyMap,
+    warnings: &mut Vec<ConfigurationDiagnostic>,
+) -> Result<Configuration, ConfigurationDiagnostic> {
+    let mut json = serde_json::to_value(&raw).unwrap();
+
+    raw.swap_remove("migrateFrom");
+    raw.swap_remove("preset");
     raw.swap_remove("aliases");
     raw.swap_remove("groups");
+    raw.swap_remove("assetExtensions");
+    raw.swap_remove("unmatched");
+    raw.swap_remove("matchStrategy");
+    raw.swap_remove("sortOrder");
+    raw.swap_remove("separateGroups");
+    raw.swap_remove("packageJson");
+    raw.swap_remove("importMap");
+    raw.swap_remove("removeUnused");
+    raw.swap_remove("keepSideEffects");
+    raw.swap_remove("keepUnused");
+    raw.swap_remove("normalize");
+    raw.swap_remove("typeImportStyle");
+    raw.swap_remove("relativeExtensions");
+    raw.swap_remove("aliasPaths");
+    raw.swap_remove("aliasRewrite");
+    raw.swap_remove("builtinPrefix");
+    raw.swap_remove("strict");
+    raw.swap_remove("overrides");
+
+    if let Some(value) = json.as_object_mut().unwrap().remove("migrateFrom") {
+        let migrate_from: MigrateFrom = match serde_json::from_value(value) {
+            Ok(it) => it,
+            Err(err) => {
+                return Err(ConfigurationDiagnostic {
+                    property_name: "migrateFrom".to_string(),
+                    message: err.to_string(),
+                });
+            }
+        };
+
+        if let Some(options) = migrate_from.import_order {
+            let conversion = convert_import_order(&options);
+
+            warnings.extend(conversion.warnings.into_iter().map(|message| {
+                ConfigurationDiagnostic {
+                    property_name: "migrateFrom.importOrder".to_string(),
+                    message,
+                }
+            }));
+
+            let json = json.as_object_mut().unwrap();
+            for (key, value) in conversion.options {
+                json.entry(key).or_insert(value);
+            }
+        }
+    }
+
+    if let Some(preset) = json.get("preset") {
+        let preset: Preset = match serde_json::from_value(preset.clone()) {
+            Ok(it) => it,
+            Err(err) => {
+                return Err(ConfigurationDiagnostic {
+                    property_name: "preset".to_string(),
+                    message: err.to_string(),
+                });
+            }
+        };
+
+        let json = json.as_object_mut().unwrap();
+        for (key, value) in preset.options() {
+            json.entry(key).or_insert(value);
+        }
+    }
 
     let mut config: Configuration = match serde_path_to_error::deserialize(json) {
         Ok(it) => it,
@@ -83,5 +295,146 @@ pub fn resolve_config(raw: &mut ConfigKeyMap) -> Result<Configuration, Configura
         config.groups = get_default_groups();
     }
 
+    validate_groups(&config.groups)?;
+
+    for (index, item) in config.overrides.iter_mut().enumerate() {
+        if item.aliases.as_ref().is_some_and(Vec::is_empty) {
+            item.aliases = Some(get_default_aliases());
+        }
+
+        if item.groups.as_ref().is_some_and(Vec::is_empty) {
+            item.groups = Some(get_default_groups());
+        }
+
+        if let Some(groups) = &item.groups {
+            validate_groups(groups).map_err(|err| ConfigurationDiagnostic {
+                property_name: format!("overrides[{index}].{}", err.property_name),
+                message: format!("overrides[{index}]: {}", err.message),
+            })?;
+        }
+    }
+
+    validate_overrides(&config.overrides)?;
+
+    let lints = lint_config(&config);
+
+    for (index, item) in config.overrides.iter().enumerate() {
+        let mut resolved = config.clone();
+        item.apply(&mut resolved);
+
+        // Only the problems introduced by the override are reported again
+        for lint in lint_config(&resolved) {
+            if !lints.iter().any(|it| it.message == lint.message) {
+                warnings.push(ConfigurationDiagnostic {
+                    property_name: format!("overrides[{index}].{}", lint.property_name),
+                    message: format!("overrides[{index}]: {}", lint.message),
+                });
+            }
+        }
+    }
+
+    warnings.extend(lints);
+
     Ok(config)
 }
+
+fn validate_overrides(overrides: &[ConfigOverride]) -> Result<(), ConfigurationDiagnostic> {
+    for (index, item) in overrides.iter().enumerate() {
+        // Overrides for the same files are applied in order, so a later one hides an earlier one
+        let same_files = overrides[..index]
+            .iter()
+            .enumerate()
+            .filter(|(_, it)| it.files.pattern() == item.files.pattern());
+
+        for (first, it) in same_files {
+            let properties = it.properties();
+            let conflict = item
+                .properties()
+                .into_iter()
+                .find(|p| properties.contains(p));
+
+            if let Some(property) = conflict {
+                return Err(ConfigurationDiagnostic {
+                    property_name: format!("overrides[{index}].{property}"),
+                    message: format!(
+                        "overrides[{index}] and overrides[{first}] both set `{property}` for \"{}\"",
+                        item.files.pattern(),
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_groups(groups: &[ImportGroup]) -> Result<(), ConfigurationDiagnostic> {
+    let mut unmatched: Option<usize> = None;
+
+    for (index, group) in groups.iter().enumerate() {
+        let is_unmatched = |rule: &Rule| matches!(rule, Rule::Unmatched);
+
+        let misplaced = group
+            .include
+            .iter()
+            .filter(|rule| !is_unmatched(rule))
+            .chain(group.exclude.iter())
+            .chain(group.order.iter().filter_map(|entry| match entry {
+                OrderEntry::Rule(rule) => Some(rule),
+                OrderEntry::Rest => None,
+            }))
+            .any(|rule| rule.contains(&is_unmatched));
+
+        if misplaced {
+            return Err(ConfigurationDiagnostic {
+                property_name: format!("groups[{index}]"),
+                message: format!(
+                    "{}: <unmatched> can only be used directly in `include`",
+                    group.label(index),
+                ),
+            });
+        }
+
+        if group.includes_unmatched() {
+            if let Some(first) = unmatched {
+                return Err(ConfigurationDiagnostic {
+                    property_name: format!("groups[{index}]"),
+                    message: format!(
+                        "{}: <unmatched> is already included by {}",
+                        group.label(index),
+                        groups[first].label(first),
+                    ),
+                });
+            }
+
+            unmatched = Some(index);
+        }
+
+        let rests = group
+            .order
+            .iter()
+            .filter(|it| matches!(it, OrderEntry::Rest));
+        if rests.count() > 1 {
+            return Err(ConfigurationDiagnostic {
+                property_name: format!("groups[{index}].order"),
+                message: format!("{}: \"...\" can only be used once", group.label(index)),
+            });
+        }
+
+        let duplicate = groups[..index]
+            .iter()
+            .position(|it| it.name.is_some() && it.name == group.name);
+
+        if let Some(first) = duplicate {
+            return Err(ConfigurationDiagnostic {
+                property_name: format!("groups[{index}].name"),
+                message: format!(
+                    "{} has the same name as groups[{first}]",
+                    group.label(index)
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// Switches of rewriting degenerate import forms into their canonical forms.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Normalize {
+    /// `import {} from "x"` to `import "x"`, and `import X, {} from "x"` to `import X from "x"`.
+    #[serde(default)]
+    pub empty_braces: bool,
+    /// `import { default as X } from "x"` to `import X from "x"`.
+    #[serde(default)]
+    pub default_specifier: bool,
+    /// `import { a, a as a } from "x"` to `import { a } from "x"`.
+    #[serde(default)]
+    pub duplicate_specifiers: bool,
+}
+
+/// How to write the type-only specifiers of an import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TypeImportStyle {
+    /// Keep them as they are written.
+    #[default]
+    Preserve,
+    /// `import { type A, b } from "x"`, merging `import type` into the value import of the module.
+    Inline,
+    /// `import type { A } from "x"`, split from the value import of the module.
+    Separate,
+}
+
+/// Whether relative module specifiers are written with extensions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelativeExtensions {
+    /// Keep them as they are written.
+    #[default]
+    Preserve,
+    /// `./utils.js`, as required by `nodenext` module resolution.
+    Always,
+    /// `./utils`, as preferred by bundlers.
+    Never,
+}
+
+/// Whether builtin module specifiers are written with the `node:` prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuiltinPrefix {
+    /// Keep them as they are written.
+    #[default]
+    Preserve,
+    /// `node:fs`.
+    Add,
+    /// `fs`, except the modules only available with the prefix like `node:test`.
+    Remove,
+}
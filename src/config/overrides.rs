@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AliasPaths, AliasRewrite, BuiltinPrefix, Configuration, CustomRule, Glob, ImportGroup,
+    ImportMap, MatchStrategy, Normalize, PackageJson, RelativeExtensions, SortOrder,
+    TypeImportStyle, UnmatchedImports,
+};
+
+/// A partial config applied to the files matched by `files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConfigOverride {
+    pub files: Glob,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<CustomRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<ImportGroup>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_extensions: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unmatched: Option<UnmatchedImports>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_strategy: Option<MatchStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<SortOrder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separate_groups: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_json: Option<PackageJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_map: Option<ImportMap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove_unused: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_side_effects: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_unused: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_import_style: Option<TypeImportStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_extensions: Option<RelativeExtensions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_paths: Option<AliasPaths>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_rewrite: Option<AliasRewrite>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builtin_prefix: Option<BuiltinPrefix>,
+}
+
+impl ConfigOverride {
+    /// The names of the properties set by this override.
+    pub fn properties(&self) -> Vec<&'static str> {
+        let mut properties = Vec::new();
+
+        if self.aliases.is_some() {
+            properties.push("aliases");
+        }
+        if self.groups.is_some() {
+            properties.push("groups");
+        }
+        if self.asset_extensions.is_some() {
+            properties.push("assetExtensions");
+        }
+        if self.unmatched.is_some() {
+            properties.push("unmatched");
+        }
+        if self.match_strategy.is_some() {
+            properties.push("matchStrategy");
+        }
+        if self.sort_order.is_some() {
+            properties.push("sortOrder");
+        }
+        if self.separate_groups.is_some() {
+            properties.push("separateGroups");
+        }
+        if self.package_json.is_some() {
+            properties.push("packageJson");
+        }
+        if self.import_map.is_some() {
+            properties.push("importMap");
+        }
+        if self.remove_unused.is_some() {
+            properties.push("removeUnused");
+        }
+        if self.keep_side_effects.is_some() {
+            properties.push("keepSideEffects");
+        }
+        if self.keep_unused.is_some() {
+            properties.push("keepUnused");
+        }
+        if self.normalize.is_some() {
+            properties.push("normalize");
+        }
+        if self.type_import_style.is_some() {
+            properties.push("typeImportStyle");
+        }
+        if self.relative_extensions.is_some() {
+            properties.push("relativeExtensions");
+        }
+        if self.alias_paths.is_some() {
+            properties.push("aliasPaths");
+        }
+        if self.alias_rewrite.is_some() {
+            properties.push("aliasRewrite");
+        }
+        if self.builtin_prefix.is_some() {
+            properties.push("builtinPrefix");
+        }
+
+        properties
+    }
+
+    pub fn apply(&self, config: &mut Configuration) {
+        if let Some(aliases) = &self.aliases {
+            config.aliases = aliases.clone();
+        }
+        if let Some(groups) = &self.groups {
+            config.groups = groups.clone();
+        }
+        if let Some(asset_extensions) = &self.asset_extensions {
+            config.asset_extensions = asset_extensions.clone();
+        }
+        if let Some(unmatched) = self.unmatched {
+            config.unmatched = unmatched;
+        }
+        if let Some(match_strategy) = self.match_strategy {
+            config.match_strategy = match_strategy;
+        }
+        if let Some(sort_order) = self.sort_order {
+            config.sort_order = sort_order;
+        }
+        if let Some(separate_groups) = self.separate_groups {
+            config.separate_groups = separate_groups;
+        }
+        if let Some(package_json) = &self.package_json {
+            config.package_json = Some(package_json.clone());
+        }
+        if let Some(import_map) = &self.import_map {
+            config.import_map = Some(import_map.clone());
+        }
+        if let Some(remove_unused) = self.remove_unused {
+            config.remove_unused = remove_unused;
+        }
+        if let Some(keep_side_effects) = self.keep_side_effects {
+            config.keep_side_effects = keep_side_effects;
+        }
+        if let Some(keep_unused) = &self.keep_unused {
+            config.keep_unused = keep_unused.clone();
+        }
+        if let Some(normalize) = self.normalize {
+            config.normalize = normalize;
+        }
+        if let Some(type_import_style) = self.type_import_style {
+            config.type_import_style = type_import_style;
+        }
+        if let Some(relative_extensions) = self.relative_extensions {
+            config.relative_extensions = relative_extensions;
+        }
+        if let Some(alias_paths) = &self.alias_paths {
+            config.alias_paths = Some(alias_paths.clone());
+        }
+        if let Some(alias_rewrite) = self.alias_rewrite {
+            config.alias_rewrite = alias_rewrite;
+        }
+        if let Some(builtin_prefix) = self.builtin_prefix {
+            config.builtin_prefix = builtin_prefix;
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The fields of package.json used by the dependency rules. Other fields are ignored, so the whole
+/// package.json can be pasted in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dev_dependencies: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub peer_dependencies: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub optional_dependencies: BTreeMap<String, Value>,
+    /// The subpath imports like `#db`, mapping to relative paths or packages.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub imports: Map<String, Value>,
+}
+
+impl PackageJson {
+    /// Whether the package is a dependency, including the optional ones.
+    pub fn is_dependency(&self, package: &str) -> bool {
+        self.dependencies.contains_key(package) || self.optional_dependencies.contains_key(package)
+    }
+
+    pub fn is_dev_dependency(&self, package: &str) -> bool {
+        self.dev_dependencies.contains_key(package)
+    }
+
+    pub fn is_peer_dependency(&self, package: &str) -> bool {
+        self.peer_dependencies.contains_key(package)
+    }
+
+    pub fn is_self(&self, package: &str) -> bool {
+        self.name.as_deref() == Some(package)
+    }
+
+    /// Resolves a subpath import with `imports`. Conditional targets resolve to the first target in
+    /// the order they are written.
+    pub fn resolve_import(&self, specifier: &str) -> Option<String> {
+        if let Some(target) = self.imports.get(specifier) {
+            return first_target(target).map(str::to_string);
+        }
+
+        // Like Node.js, the pattern with the longest prefix wins
+        let (_, target, matched) = self
+            .imports
+            .iter()
+            .filter_map(|(key, target)| {
+                let (prefix, suffix) = key.split_once('*')?;
+                let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((prefix.len(), target, matched))
+            })
+            .max_by_key(|(len, _, _)| *len)?;
+
+        Some(first_target(target)?.replace('*', matched))
+    }
+
+    pub fn is_declared(&self, package: &str) -> bool {
+        self.is_dependency(package)
+            || self.is_dev_dependency(package)
+            || self.is_peer_dependency(package)
+            || self.is_self(package)
+    }
+}
+
+fn first_target(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(target) => Some(target),
+        Value::Array(targets) => targets.iter().find_map(first_target),
+        Value::Object(conditions) => conditions.values().find_map(first_target),
+        _ => None,
+    }
+}
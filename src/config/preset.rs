@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Reproduces the order of imports produced by other tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Preset {
+    /// The defaults of `import/order` in eslint-plugin-import.
+    ImportOrder,
+    /// The defaults of @trivago/prettier-plugin-sort-imports.
+    Trivago,
+    /// The defaults of eslint-plugin-simple-import-sort.
+    SimpleImportSort,
+    /// The "Organize Imports" command of TypeScript.
+    OrganizeImports,
+}
+
+impl Preset {
+    /// The options set by the preset, which are overridden by the ones set in the config.
+    pub fn options(self) -> Map<String, Value> {
+        let options = match self {
+            // Types not listed in the default `groups` are placed together in the last group.
+            // Imports are not alphabetized, and no blank lines are enforced between groups.
+            Preset::ImportOrder => json!({
+                "groups": [
+                    { "include": ["<builtin>"] },
+                    { "include": ["<npm>"] },
+                    { "include": ["<parent>"] },
+                    { "include": ["<sibling>"] },
+                    { "include": ["<index>"] },
+                    { "include": ["<unmatched>"] },
+                ],
+                "sortOrder": "none",
+                "separateGroups": false,
+            }),
+            // Without `importOrder`, all imports are third party modules.
+            Preset::Trivago => json!({
+                "groups": [
+                    { "include": ["<unmatched>"] },
+                ],
+                "sortOrder": "caseSensitive",
+                "separateGroups": false,
+            }),
+            // The default groups are side effect imports, `node:` builtins, packages, absolute
+            // imports and other imports, and relative imports. Side effect imports are not sorted.
+            Preset::SimpleImportSort => json!({
+                "groups": [
+                    { "include": ["<effect>"], "sortOrder": "none" },
+                    { "include": [{ "prefix": "node:" }] },
+                    { "include": ["^@?\\w"] },
+                    { "include": ["<unmatched>"] },
+                    { "include": ["^\\."] },
+                ],
+                "sortOrder": "caseInsensitive",
+                "separateGroups": true,
+            }),
+            // Relative imports are placed after the others, with no blank line.
+            Preset::OrganizeImports => json!({
+                "groups": [
+                    { "include": ["<unmatched>"], "order": ["...", "<relative>"] },
+                ],
+                "sortOrder": "caseInsensitive",
+                "separateGroups": false,
+            }),
+        };
+
+        match options {
+            Value::Object(options) => options,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::format::format_with_config;
+
+    fn format(preset: &str, src: &str) -> String {
+        format_with_config(json!({ "preset": preset }), "src/index.ts", src)
+    }
+
+    const SOURCE: &str = r#"import { b } from "./b";
+import "./polyfill";
+import z from "zod";
+import { a } from "../a";
+import React from "react";
+import "@babel/register";
+import { readFile } from "node:fs";
+import path from "path";
+import { Foo } from "Foo";
+import { c } from "~/c";
+"#;
+
+    #[test]
+    fn import_order() {
+        let expected = r#"import { readFile } from "node:fs";
+import path from "path";
+import z from "zod";
+import React from "react";
+import "@babel/register";
+import { Foo } from "Foo";
+import { a } from "../a";
+import { b } from "./b";
+import "./polyfill";
+import { c } from "~/c";
+"#;
+        assert_eq!(format("importOrder", SOURCE), expected);
+    }
+
+    #[test]
+    fn trivago() {
+        let expected = r#"import { a } from "../a";
+import { b } from "./b";
+import "./polyfill";
+import "@babel/register";
+import { Foo } from "Foo";
+import { readFile } from "node:fs";
+import path from "path";
+import React from "react";
+import z from "zod";
+import { c } from "~/c";
+"#;
+        assert_eq!(format("trivago", SOURCE), expected);
+    }
+
+    #[test]
+    fn simple_import_sort() {
+        let expected = r#"import "./polyfill";
+import "@babel/register";
+
+import { readFile } from "node:fs";
+
+import { Foo } from "Foo";
+import path from "path";
+import React from "react";
+import z from "zod";
+
+import { c } from "~/c";
+
+import { a } from "../a";
+import { b } from "./b";
+"#;
+        assert_eq!(format("simpleImportSort", SOURCE), expected);
+    }
+
+    #[test]
+    fn organize_imports() {
+        let expected = r#"import "@babel/register";
+import { Foo } from "Foo";
+import { readFile } from "node:fs";
+import path from "path";
+import React from "react";
+import z from "zod";
+import { c } from "~/c";
+import { a } from "../a";
+import { b } from "./b";
+import "./polyfill";
+"#;
+        assert_eq!(format("organizeImports", SOURCE), expected);
+    }
+
+    #[test]
+    fn letter_case() {
+        let src = r#"import b from "b";
+import _ from "_x";
+import B from "B";
+import a from "a";
+import A from "A";
+"#;
+
+        // The collator of simple-import-sort puts uppercase first when the letters are equal
+        let expected = r#"import _ from "_x";
+import A from "A";
+import a from "a";
+import B from "B";
+import b from "b";
+"#;
+        assert_eq!(format("simpleImportSort", src), expected);
+
+        let expected = r#"import A from "A";
+import B from "B";
+import _ from "_x";
+import a from "a";
+import b from "b";
+"#;
+        assert_eq!(format("trivago", src), expected);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::{Rule, RuleSet};
+
+/// The serialized form of a rule, which is either a string or an object.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RawRule {
+    String(String),
+    Object(RuleObject),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RuleObject {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packages: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all: Option<RuleSet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub any: Option<RuleSet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<Rule>>,
+}
+
+impl<'de> Deserialize<'de> for RawRule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Not using `#[serde(untagged)]` here because it swallows the errors of the object form
+        match Value::deserialize(deserializer)? {
+            Value::String(value) => Ok(RawRule::String(value)),
+            value @ Value::Object(_) => match serde_json::from_value(value) {
+                Ok(object) => Ok(RawRule::Object(object)),
+                Err(err) => Err(D::Error::custom(err)),
+            },
+            _ => Err(D::Error::custom("expected a string or an object")),
+        }
+    }
+}
@@ -1,17 +1,37 @@
-use anyhow::{Error, Ok, Result};
+use anyhow::{bail, Error, Ok, Result};
 use serde::{Deserialize, Serialize};
 
-use super::CustomRule;
+use super::{CustomRule, RawRule, RuleObject, RuleSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(try_from = "String", into = "String")]
+#[serde(try_from = "RawRule", into = "RawRule")]
 pub enum Rule {
     Effect,
     Builtin,
     Relative,
+    Parent,
+    Sibling,
+    Index,
+    Subpath,
     Alias,
     Npm,
+    Dependency,
+    DevDependency,
+    PeerDependency,
+    SelfPackage,
+    Undeclared,
+    Asset,
+    Default,
+    Namespace,
+    Named,
+    Mixed,
+    Type,
+    With { key: String, value: Option<String> },
+    Unmatched,
     Custom(CustomRule),
+    All(RuleSet),
+    Any(RuleSet),
+    Not(Box<Rule>),
 }
 
 impl Rule {
@@ -19,13 +39,121 @@ impl Rule {
         let rule = CustomRule::try_from(pattern)?;
         Ok(Rule::Custom(rule))
     }
+
+    pub fn with(attribute: &str) -> Result<Rule> {
+        let (key, value) = match attribute.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim().to_string())),
+            None => (attribute.trim(), None),
+        };
+
+        if key.is_empty() {
+            bail!("missing import attribute key");
+        }
+
+        Ok(Rule::With {
+            key: key.to_string(),
+            value,
+        })
+    }
+
+    /// How specific the rule is when it matches, used by the `specificity` match strategy.
+    /// For `all` and `any`, it depends on which of the nested rules match the import.
+    pub fn specificity(&self) -> Specificity {
+        match self {
+            Rule::Unmatched => Specificity::Unmatched,
+            Rule::Custom(CustomRule::Packages(_)) => Specificity::Package,
+            Rule::Custom(_) => Specificity::Pattern,
+            _ => Specificity::Category,
+        }
+    }
+
+    /// Returns whether the rule or any of its nested rules satisfies the predicate.
+    pub fn contains(&self, predicate: &impl Fn(&Rule) -> bool) -> bool {
+        if predicate(self) {
+            return true;
+        }
+
+        match self {
+            Rule::All(rules) | Rule::Any(rules) => rules.iter().any(|it| it.contains(predicate)),
+            Rule::Not(rule) => rule.contains(predicate),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Specificity {
+    Unmatched,
+    /// A predefined rule, or a combinator negating another rule.
+    Category,
+    /// A regex, glob or prefix.
+    Pattern,
+    /// A literal package name.
+    Package,
 }
 
-impl TryFrom<String> for Rule {
+impl TryFrom<RawRule> for Rule {
     type Error = Error;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        Self::try_from(&*value)
+    fn try_from(value: RawRule) -> Result<Self, Self::Error> {
+        match value {
+            RawRule::String(value) => Self::try_from(&*value),
+            RawRule::Object(value) => Self::try_from(value),
+        }
+    }
+}
+
+impl TryFrom<RuleObject> for Rule {
+    type Error = Error;
+
+    fn try_from(value: RuleObject) -> Result<Self, Self::Error> {
+        match value {
+            RuleObject {
+                all: None,
+                any: None,
+                not: None,
+                ..
+            } => Ok(Rule::Custom(CustomRule::try_from(value)?)),
+            RuleObject { regex: Some(_), .. }
+            | RuleObject { flags: Some(_), .. }
+            | RuleObject {
+                packages: Some(_), ..
+            }
+            | RuleObject {
+                prefix: Some(_), ..
+            } => bail!("`all`, `any` and `not` cannot be combined with other fields"),
+            RuleObject {
+                all: Some(rules),
+                any: None,
+                not: None,
+                ..
+            } => {
+                if rules.is_empty() {
+                    bail!("`all` must not be empty");
+                }
+
+                Ok(Rule::All(rules))
+            }
+            RuleObject {
+                all: None,
+                any: Some(rules),
+                not: None,
+                ..
+            } => {
+                if rules.is_empty() {
+                    bail!("`any` must not be empty");
+                }
+
+                Ok(Rule::Any(rules))
+            }
+            RuleObject {
+                all: None,
+                any: None,
+                not: Some(rule),
+                ..
+            } => Ok(Rule::Not(rule)),
+            _ => bail!("a rule object must have only one of `all`, `any` or `not`"),
+        }
     }
 }
 
@@ -37,22 +165,82 @@ impl TryFrom<&str> for Rule {
             "<effect>" => Ok(Rule::Effect),
             "<builtin>" => Ok(Rule::Builtin),
             "<relative>" => Ok(Rule::Relative),
+            "<parent>" => Ok(Rule::Parent),
+            "<sibling>" => Ok(Rule::Sibling),
+            "<index>" => Ok(Rule::Index),
+            "<subpath>" => Ok(Rule::Subpath),
             "<alias>" => Ok(Rule::Alias),
             "<npm>" => Ok(Rule::Npm),
-            x => Rule::regex(x),
+            "<dependency>" => Ok(Rule::Dependency),
+            "<devDependency>" => Ok(Rule::DevDependency),
+            "<peerDependency>" => Ok(Rule::PeerDependency),
+            "<self>" => Ok(Rule::SelfPackage),
+            "<undeclared>" => Ok(Rule::Undeclared),
+            "<asset>" => Ok(Rule::Asset),
+            "<default>" => Ok(Rule::Default),
+            "<namespace>" => Ok(Rule::Namespace),
+            "<named>" => Ok(Rule::Named),
+            "<mixed>" => Ok(Rule::Mixed),
+            "<type>" => Ok(Rule::Type),
+            "<unmatched>" => Ok(Rule::Unmatched),
+            x => match x.strip_prefix("<with:").and_then(|x| x.strip_suffix('>')) {
+                Some(attribute) => Rule::with(attribute),
+                None => Rule::regex(x),
+            },
         }
     }
 }
 
-impl From<Rule> for String {
+impl From<Rule> for RawRule {
     fn from(value: Rule) -> Self {
-        match value {
+        let value = match value {
             Rule::Effect => "<effect>".to_string(),
             Rule::Builtin => "<builtin>".to_string(),
             Rule::Relative => "<relative>".to_string(),
+            Rule::Parent => "<parent>".to_string(),
+            Rule::Sibling => "<sibling>".to_string(),
+            Rule::Index => "<index>".to_string(),
+            Rule::Subpath => "<subpath>".to_string(),
             Rule::Alias => "<alias>".to_string(),
             Rule::Npm => "<npm>".to_string(),
-            Rule::Custom(rule) => rule.into(),
-        }
+            Rule::Dependency => "<dependency>".to_string(),
+            Rule::DevDependency => "<devDependency>".to_string(),
+            Rule::PeerDependency => "<peerDependency>".to_string(),
+            Rule::SelfPackage => "<self>".to_string(),
+            Rule::Undeclared => "<undeclared>".to_string(),
+            Rule::Asset => "<asset>".to_string(),
+            Rule::Default => "<default>".to_string(),
+            Rule::Namespace => "<namespace>".to_string(),
+            Rule::Named => "<named>".to_string(),
+            Rule::Mixed => "<mixed>".to_string(),
+            Rule::Type => "<type>".to_string(),
+            Rule::Unmatched => "<unmatched>".to_string(),
+            Rule::With { key, value: None } => format!("<with:{key}>"),
+            Rule::With {
+                key,
+                value: Some(value),
+            } => format!("<with:{key}={value}>"),
+            Rule::Custom(rule) => return rule.into(),
+            Rule::All(rules) => {
+                return RawRule::Object(RuleObject {
+                    all: Some(rules),
+                    ..Default::default()
+                })
+            }
+            Rule::Any(rules) => {
+                return RawRule::Object(RuleObject {
+                    any: Some(rules),
+                    ..Default::default()
+                })
+            }
+            Rule::Not(rule) => {
+                return RawRule::Object(RuleObject {
+                    not: Some(rule),
+                    ..Default::default()
+                })
+            }
+        };
+
+        RawRule::String(value)
     }
 }
@@ -13,6 +13,10 @@ impl RuleSet {
         Self { rules: vec![rule] }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &'_ Rule> {
         self.rules.iter()
     }
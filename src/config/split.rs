@@ -0,0 +1,101 @@
+use regex::Regex;
+use serde::de::Error;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// How to split the imports of a group into sub-groups.
+#[derive(Debug, Clone)]
+pub enum SplitBy {
+    /// The npm scope, e.g. `@babel` for `@babel/core`.
+    Scope,
+    /// The first directory, e.g. `./components` for `./components/Button`.
+    Directory,
+    /// A named capture group of a regex.
+    Capture { regex: Regex, capture: String },
+}
+
+impl SplitBy {
+    pub fn key(&self, module: &str) -> Option<String> {
+        match self {
+            SplitBy::Scope => {
+                let (scope, _) = module.split_once('/')?;
+                scope.starts_with('@').then(|| scope.to_string())
+            }
+            SplitBy::Directory => {
+                let segments: Vec<_> = module.split('/').collect();
+
+                // Skip the leading segments like `.`, `..`, or `@` and `~` of aliases
+                let first = segments
+                    .iter()
+                    .position(|it| it.chars().any(char::is_alphanumeric))
+                    .unwrap_or(segments.len());
+
+                // Modules directly under the leading segments are not in any directory
+                let end = if first + 1 < segments.len() {
+                    first + 1
+                } else {
+                    first
+                };
+
+                (end > 0).then(|| segments[..end].join("/"))
+            }
+            SplitBy::Capture { regex, capture } => {
+                let captures = regex.captures(module)?;
+                Some(captures.name(capture)?.as_str().to_string())
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CaptureObject {
+    regex: String,
+    capture: String,
+}
+
+impl<'de> Deserialize<'de> for SplitBy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = match Value::deserialize(deserializer)? {
+            Value::String(value) if value == "scope" => return Ok(SplitBy::Scope),
+            Value::String(value) if value == "directory" => return Ok(SplitBy::Directory),
+            value @ Value::Object(_) => serde_json::from_value::<CaptureObject>(value),
+            _ => {
+                return Err(D::Error::custom(
+                    "expected \"scope\", \"directory\" or { \"regex\": ..., \"capture\": ... }",
+                ))
+            }
+        };
+
+        let object = object.map_err(D::Error::custom)?;
+        let regex = Regex::new(&object.regex).map_err(D::Error::custom)?;
+
+        if !regex.capture_names().any(|it| it == Some(&object.capture)) {
+            return Err(D::Error::custom(format!(
+                "the regex has no capture group named `{}`",
+                object.capture,
+            )));
+        }
+
+        Ok(SplitBy::Capture {
+            regex,
+            capture: object.capture,
+        })
+    }
+}
+
+impl Serialize for SplitBy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SplitBy::Scope => serializer.serialize_str("scope"),
+            SplitBy::Directory => serializer.serialize_str("directory"),
+            SplitBy::Capture { regex, capture } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("regex", regex.as_str())?;
+                map.serialize_entry("capture", capture)?;
+                map.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::path::{Component, Path};
+
+use crate::config::{AliasPaths, Configuration};
+
+use super::ImportElement;
+
+/// Rewrites a relative specifier climbing too many parent directories to an alias, or the alias of
+/// a module in the directory of the importing file to a relative specifier.
+pub fn rewrite_alias(element: &mut ImportElement<'_>, config: &Configuration, path: &Path) {
+    let Some(alias_paths) = &config.alias_paths else {
+        return;
+    };
+
+    let Some(directory) = path.parent() else {
+        return;
+    };
+
+    let directory: Vec<_> = directory
+        .components()
+        .filter_map(|it| match it {
+            Component::Normal(segment) => segment.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    let module = element.module();
+    let rewrite = config.alias_rewrite;
+
+    let rewritten = if module.starts_with('.') {
+        rewrite
+            .parent_depth
+            .and_then(|depth| to_alias(module, depth, &directory, alias_paths))
+    } else if rewrite.same_directory {
+        to_relative(module, &directory, alias_paths)
+    } else {
+        None
+    };
+
+    if let Some(rewritten) = rewritten {
+        element.rewritten_module = Some(rewritten);
+    }
+}
+
+fn to_alias(
+    module: &str,
+    depth: usize,
+    directory: &[&str],
+    alias_paths: &AliasPaths,
+) -> Option<String> {
+    // Queries and fragments are kept as is
+    let (path, suffix) = module.split_at(module.find(['?', '#']).unwrap_or(module.len()));
+
+    let mut target = directory.to_vec();
+    let mut climbed = 0;
+
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                target.pop()?;
+                climbed = climbed.max(directory.len().saturating_sub(target.len()));
+            }
+            segment => target.push(segment),
+        }
+    }
+
+    if climbed <= depth {
+        return None;
+    }
+
+    let trailing = if path.ends_with('/') { "/" } else { "" };
+
+    // The alias of the deepest directory wins, e.g. `@ui/` of `src/ui` over `@/` of `src`
+    let (_, alias) = alias_paths
+        .directories()
+        .filter_map(|(prefix, segments)| {
+            let start = rfind(&target, &segments)?;
+
+            // The project root must contain the importing file
+            if !directory.starts_with(&target[..start]) {
+                return None;
+            }
+
+            let rest = &target[start + segments.len()..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            let alias = format!("{prefix}{}{trailing}{suffix}", rest.join("/"));
+            Some((segments.len(), alias))
+        })
+        .max_by_key(|(len, _)| *len)?;
+
+    Some(alias)
+}
+
+fn to_relative(module: &str, directory: &[&str], alias_paths: &AliasPaths) -> Option<String> {
+    let (_, relative) = alias_paths
+        .directories()
+        .filter_map(|(prefix, segments)| {
+            let rest = module.strip_prefix(prefix)?;
+            if rest.split('/').any(|it| matches!(it, "" | "." | "..")) {
+                return None;
+            }
+
+            // The directory of the alias is located by its path in the importing file's path
+            let start = rfind(directory, &segments)?;
+            let mut target = directory[..start + segments.len()].to_vec();
+            target.extend(rest.split('/'));
+
+            // Only the modules right in the directory of the importing file
+            let (name, parent) = target.split_last()?;
+            if parent != directory {
+                return None;
+            }
+
+            Some((prefix.len(), format!("./{name}")))
+        })
+        .max_by_key(|(len, _)| *len)?;
+
+    Some(relative)
+}
+
+/// Finds the last position of the segments in the path.
+fn rfind(path: &[&str], segments: &[&str]) -> Option<usize> {
+    let last = path.len().checked_sub(segments.len())?;
+    (0..=last)
+        .rev()
+        .find(|&start| path[start..start + segments.len()] == *segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn alias_paths() -> AliasPaths {
+        serde_json::from_value(json!({ "@/*": ["./src/*"], "@ui/*": ["./src/ui/*"] })).unwrap()
+    }
+
+    fn alias(module: &str, depth: usize, directory: &str) -> Option<String> {
+        let directory: Vec<_> = directory.split('/').collect();
+        to_alias(module, depth, &directory, &alias_paths())
+    }
+
+    fn relative(module: &str, directory: &str) -> Option<String> {
+        let directory: Vec<_> = directory.split('/').collect();
+        to_relative(module, &directory, &alias_paths())
+    }
+
+    #[test]
+    fn rewrite_to_alias() {
+        let directory = "home/me/app/src/pages/settings/profile";
+        let module = "../../../components/Button";
+        assert_eq!(
+            alias(module, 2, directory).as_deref(),
+            Some("@/components/Button")
+        );
+        assert_eq!(alias(module, 3, directory), None);
+
+        // Trailing slashes, queries and fragments are kept
+        let module = "../../../components/";
+        assert_eq!(
+            alias(module, 2, directory).as_deref(),
+            Some("@/components/")
+        );
+        let module = "../../../styles/a.css?raw";
+        assert_eq!(
+            alias(module, 2, directory).as_deref(),
+            Some("@/styles/a.css?raw")
+        );
+    }
+
+    #[test]
+    fn count_climbed_directories() {
+        let directory = "app/src/pages/settings";
+
+        // The deepest directory climbed to counts, not the number of `..`
+        assert_eq!(alias("../a/../../b", 1, directory).as_deref(), Some("@/b"));
+        assert_eq!(alias("../a/../../b", 2, directory), None);
+        assert_eq!(
+            alias("./a/../../b", 0, directory).as_deref(),
+            Some("@/pages/b")
+        );
+
+        // Climbing out of the file system
+        assert_eq!(alias("../../../../../a", 0, directory), None);
+    }
+
+    #[test]
+    fn prefer_deepest_alias() {
+        let directory = "app/src/pages/a";
+        assert_eq!(
+            alias("../../ui/Button", 1, directory).as_deref(),
+            Some("@ui/Button")
+        );
+        assert_eq!(
+            alias("../../utils", 1, directory).as_deref(),
+            Some("@/utils")
+        );
+
+        // The alias of a directory is not the directory itself
+        assert_eq!(alias("../../../src", 0, directory), None);
+    }
+
+    #[test]
+    fn require_project_root() {
+        // `lib/src` is not the `src` of the project containing the importing file
+        assert_eq!(alias("../../lib/src/x", 1, "app/a/b"), None);
+    }
+
+    #[test]
+    fn rewrite_to_relative() {
+        let directory = "app/src/pages/settings";
+        assert_eq!(
+            relative("@/pages/settings/Button", directory).as_deref(),
+            Some("./Button")
+        );
+
+        // Only the modules right in the directory of the importing file
+        assert_eq!(relative("@/pages/Button", directory), None);
+        assert_eq!(relative("@/pages/settings/a/Button", directory), None);
+        assert_eq!(relative("@/pages/settings/../Button", directory), None);
+        assert_eq!(relative("@/pages/settings/", directory), None);
+
+        assert_eq!(
+            relative("@ui/Button", "app/src/ui").as_deref(),
+            Some("./Button")
+        );
+        assert_eq!(relative("lodash", directory), None);
+    }
+
+    #[test]
+    fn find_last_segments() {
+        assert_eq!(rfind(&["a", "src", "b", "src"], &["src"]), Some(3));
+        assert_eq!(rfind(&["a", "src", "ui", "src"], &["src", "ui"]), Some(1));
+        assert_eq!(rfind(&["a"], &["a", "b"]), None);
+        assert_eq!(rfind(&["a", "b"], &["c"]), None);
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use crate::config::{BuiltinPrefix, Configuration};
+use crate::utils::package_name;
+
+use super::ImportElement;
+
+// node -e 'console.log(JSON.stringify(require("node:module").builtinModules))'
+static BUILTINS: LazyLock<HashSet<&str>> = LazyLock::new(|| {
+    HashSet::from([
+        "_http_agent",
+        "_http_client",
+        "_http_common",
+        "_http_incoming",
+        "_http_outgoing",
+        "_http_server",
+        "_stream_duplex",
+        "_stream_passthrough",
+        "_stream_readable",
+        "_stream_transform",
+        "_stream_wrap",
+        "_stream_writable",
+        "_tls_common",
+        "_tls_wrap",
+        "assert",
+        "assert/strict",
+        "async_hooks",
+        "buffer",
+        "child_process",
+        "cluster",
+        "console",
+        "constants",
+        "crypto",
+        "dgram",
+        "diagnostics_channel",
+        "dns",
+        "dns/promises",
+        "domain",
+        "events",
+        "fs",
+        "fs/promises",
+        "http",
+        "http2",
+        "https",
+        "inspector",
+        "inspector/promises",
+        "module",
+        "net",
+        "os",
+        "path",
+        "path/posix",
+        "path/win32",
+        "perf_hooks",
+        "process",
+        "punycode",
+        "querystring",
+        "readline",
+        "readline/promises",
+        "repl",
+        "stream",
+        "stream/consumers",
+        "stream/promises",
+        "stream/web",
+        "string_decoder",
+        "sys",
+        "timers",
+        "timers/promises",
+        "tls",
+        "trace_events",
+        "tty",
+        "url",
+        "util",
+        "util/types",
+        "v8",
+        "vm",
+        "wasi",
+        "worker_threads",
+        "zlib",
+    ])
+});
+
+/// The builtin modules that can only be imported with the `node:` prefix.
+const PREFIX_ONLY: [&str; 4] = ["sea", "sqlite", "test", "test/reporters"];
+
+pub fn is_builtin(module: &str) -> bool {
+    module.starts_with("node:") || BUILTINS.contains(module)
+}
+
+/// Adds or removes the `node:` prefix of a builtin module specifier.
+pub fn rewrite_builtin_prefix(element: &mut ImportElement<'_>, config: &Configuration) {
+    let module = element.module();
+
+    let rewritten = match config.builtin_prefix {
+        BuiltinPrefix::Preserve => None,
+        BuiltinPrefix::Add => BUILTINS.contains(module).then(|| format!("node:{module}")),
+        BuiltinPrefix::Remove => module
+            .strip_prefix("node:")
+            .filter(|name| BUILTINS.contains(name) && !PREFIX_ONLY.contains(name))
+            .map(str::to_string),
+    };
+
+    let Some(rewritten) = rewritten else {
+        return;
+    };
+
+    // Without the prefix, a package of the same name or an import map entry takes precedence
+    let name = module.strip_prefix("node:").unwrap_or(module);
+    let declared = config
+        .package_json
+        .as_ref()
+        .is_some_and(|it| it.is_declared(package_name(name)));
+    let mapped = config
+        .import_map
+        .as_ref()
+        .is_some_and(|it| it.resolve(name).is_some());
+
+    if declared || mapped {
+        return;
+    }
+
+    element.rewritten_module = Some(rewritten);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::format::format_with_config;
+
+    fn format(builtin_prefix: &str, src: &str) -> String {
+        let config =
+            json!({ "groups": [{ "include": ["<unmatched>"] }], "builtinPrefix": builtin_prefix });
+        format_with_config(config, "src/index.ts", src)
+    }
+
+    #[test]
+    fn sort_by_written_prefix() {
+        let src = r#"import path from "path";
+import fs from "node:fs";
+import lodash from "lodash";
+"#;
+
+        let expected = r#"import lodash from "lodash";
+import fs from "node:fs";
+import path from "path";
+"#;
+        assert_eq!(format("preserve", src), expected);
+    }
+
+    #[test]
+    fn sort_by_builtin_name() {
+        let src = r#"import path from "path";
+import test from "node:test";
+import lodash from "lodash";
+import fs from "node:fs";
+"#;
+
+        let expected = r#"import fs from "node:fs";
+import lodash from "lodash";
+import path from "node:path";
+import test from "node:test";
+"#;
+        assert_eq!(format("add", src), expected);
+
+        let expected = r#"import fs from "fs";
+import lodash from "lodash";
+import path from "path";
+import test from "node:test";
+"#;
+        assert_eq!(format("remove", src), expected);
+    }
+
+    #[test]
+    fn keep_comments_and_lines() {
+        let src = r#"import {
+  // used for config loading
+  readFile,
+  writeFile, // TODO remove
+} from 'fs';
+"#;
+
+        let expected = r#"import {
+  // used for config loading
+  readFile,
+  writeFile, // TODO remove
+} from 'node:fs';
+"#;
+        assert_eq!(format("add", src), expected);
+    }
+}
@@ -1,10 +1,16 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
 
-use oxc::ast::ast::{ImportDeclaration, TSModuleDeclaration, TSModuleDeclarationBody};
+use oxc::ast::ast::{
+    BindingIdentifier, ImportDeclaration, ImportDeclarationSpecifier, TSModuleDeclaration,
+    TSModuleDeclarationBody,
+};
 use oxc::ast::Comment;
-use oxc::span::Span;
+use oxc::span::{GetSpan, Span};
 use ropey::Rope;
 
+use crate::config::{BuiltinPrefix, SortOrder};
+
 use super::LineSpan;
 
 #[derive(Debug)]
@@ -26,20 +32,265 @@ pub struct ImportElement<'a> {
     pub span: Span,
     pub comments: Vec<CommentElement>,
     pub decl: &'a ImportDeclaration<'a>,
+    /// The specifiers of the declaration, or `None` for side-effect imports.
+    pub specifiers: Option<Vec<SpecifierElement<'a>>>,
+    /// Whether it is an `import type` declaration.
+    pub type_only: bool,
+    /// Whether there are comments inside the declaration, which is then kept as written except for
+    /// the module specifier, so that the comments are not lost.
+    pub inner_comments: bool,
+    /// The module specifier to write in place of the source, without quotes.
+    pub rewritten_module: Option<String>,
+    /// Whether the parts other than the module specifier differ from the source, so that the import
+    /// must be rendered again.
+    pub modified: bool,
 }
 
 impl<'a> ImportElement<'a> {
-    pub fn module(&self) -> &'a str {
-        self.decl.source.value.as_str()
+    pub fn from_ast(
+        src: &'a str,
+        span: Span,
+        comments: Vec<CommentElement>,
+        inner_comments: bool,
+        decl: &'a ImportDeclaration<'a>,
+    ) -> Self {
+        let specifiers = decl.specifiers.as_ref().map(|specifiers| {
+            specifiers
+                .iter()
+                .map(|it| SpecifierElement::from_ast(src, it))
+                .collect()
+        });
+
+        Self {
+            span,
+            comments,
+            decl,
+            specifiers,
+            type_only: decl.import_kind.is_type(),
+            inner_comments,
+            rewritten_module: None,
+            modified: false,
+        }
+    }
+
+    /// The module specifier, or the rewritten one if any.
+    pub fn module(&self) -> &str {
+        match &self.rewritten_module {
+            Some(module) => module,
+            None => self.decl.source.value.as_str(),
+        }
+    }
+
+    pub fn bindings(&self) -> ImportBindings {
+        let mut bindings = ImportBindings::default();
+
+        for specifier in self.specifiers.iter().flatten() {
+            match specifier {
+                SpecifierElement::Default(_) => bindings.default = true,
+                SpecifierElement::Namespace(_) => bindings.namespace = true,
+                SpecifierElement::Named { .. } => bindings.named = true,
+            }
+        }
+
+        bindings
     }
 
-    pub fn compare(&self, other: &Self) -> Ordering {
-        let left = self.module();
-        let right = other.module();
+    /// Removes the specifiers not satisfying the predicate, unless there are comments inside.
+    pub fn retain_specifiers(&mut self, predicate: impl FnMut(&SpecifierElement<'a>) -> bool) {
+        if self.inner_comments {
+            return;
+        }
+
+        let Some(specifiers) = &mut self.specifiers else {
+            return;
+        };
+
+        let len = specifiers.len();
+        specifiers.retain(predicate);
+        self.modified |= specifiers.len() != len;
+    }
+
+    /// The source text of the import, or the text rendered from the parts if modified. A rewritten
+    /// module specifier alone replaces the source in the text, keeping the rest as written. The
+    /// formatting of rendered imports is left to the typescript plugin.
+    pub fn render(&self, src: &'a str) -> Cow<'a, str> {
+        let original = self.span.source_text(src);
+        if !self.modified {
+            if self.rewritten_module.is_none() {
+                return Cow::Borrowed(original);
+            }
+
+            let start = (self.decl.source.span.start - self.span.start) as usize;
+            let end = (self.decl.source.span.end - self.span.start) as usize;
+            let source = self.render_source(src);
+            return Cow::Owned(format!(
+                "{}{source}{}",
+                &original[..start],
+                &original[end..]
+            ));
+        }
+
+        let mut text = String::from("import ");
+
+        if self.type_only {
+            text.push_str("type ");
+        }
+
+        if let Some(specifiers) = &self.specifiers {
+            let mut parts = Vec::new();
+            let mut named = Vec::new();
+
+            for specifier in specifiers {
+                match specifier {
+                    SpecifierElement::Default(local) => parts.push(local.name.to_string()),
+                    SpecifierElement::Namespace(local) => {
+                        parts.push(format!("* as {}", local.name))
+                    }
+                    SpecifierElement::Named {
+                        imported,
+                        local,
+                        type_only,
+                    } => {
+                        let mut text = String::new();
+                        if *type_only {
+                            text.push_str("type ");
+                        }
+                        text.push_str(imported);
+                        if *imported != local.name.as_str() {
+                            text.push_str(" as ");
+                            text.push_str(&local.name);
+                        }
+                        named.push(text);
+                    }
+                }
+            }
+
+            if !named.is_empty() {
+                parts.push(format!("{{ {} }}", named.join(", ")));
+            } else if parts.is_empty() {
+                parts.push("{}".to_string());
+            }
+
+            text.push_str(&parts.join(", "));
+            text.push_str(" from ");
+        }
+
+        text.push_str(&self.render_source(src));
+
+        if let Some(clause) = &self.decl.with_clause {
+            let entries: Vec<_> = clause
+                .with_entries
+                .iter()
+                .map(|it| it.span.source_text(src))
+                .collect();
+
+            text.push(' ');
+            text.push_str(&clause.attributes_keyword.name);
+            text.push_str(&format!(" {{ {} }}", entries.join(", ")));
+        }
+
+        if original.ends_with(';') {
+            text.push(';');
+        }
+
+        Cow::Owned(text)
+    }
+
+    /// The module specifier with quotes, or the rewritten one in the original quotes.
+    fn render_source(&self, src: &'a str) -> Cow<'a, str> {
+        let source = self.decl.source.span.source_text(src);
+        match &self.rewritten_module {
+            Some(module) => {
+                let quote = &source[..1];
+                Cow::Owned(format!("{quote}{module}{quote}"))
+            }
+            None => Cow::Borrowed(source),
+        }
+    }
+
+    pub fn compare(
+        &self,
+        other: &Self,
+        order: SortOrder,
+        builtin_prefix: BuiltinPrefix,
+    ) -> Ordering {
+        // When the prefix is rewritten, builtin modules are sorted by their names, so that `fs` and
+        // `node:fs` are placed together
+        let (left, right) = match builtin_prefix {
+            BuiltinPrefix::Preserve => (self.module(), other.module()),
+            _ => (
+                self.module().strip_prefix("node:").unwrap_or(self.module()),
+                other
+                    .module()
+                    .strip_prefix("node:")
+                    .unwrap_or(other.module()),
+            ),
+        };
+
+        let ord = match order {
+            SortOrder::CaseInsensitive => match left.to_lowercase().cmp(&right.to_lowercase()) {
+                Ordering::Equal => left.cmp(right),
+                ord => ord,
+            },
+            SortOrder::CaseSensitive => left.cmp(right),
+            SortOrder::None => return Ordering::Equal,
+        };
+
+        ord.then_with(|| self.module().cmp(other.module()))
+    }
+}
+
+/// The kinds of bindings an import declaration introduces.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportBindings {
+    pub default: bool,
+    pub namespace: bool,
+    pub named: bool,
+}
+
+#[derive(Debug)]
+pub enum SpecifierElement<'a> {
+    Default(&'a BindingIdentifier<'a>),
+    Namespace(&'a BindingIdentifier<'a>),
+    Named {
+        /// The source text of the imported name, which may be a string literal.
+        imported: &'a str,
+        local: &'a BindingIdentifier<'a>,
+        type_only: bool,
+    },
+}
+
+impl<'a> SpecifierElement<'a> {
+    pub fn from_ast(src: &'a str, specifier: &'a ImportDeclarationSpecifier<'a>) -> Self {
+        match specifier {
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(it) => Self::Default(&it.local),
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(it) => Self::Namespace(&it.local),
+            ImportDeclarationSpecifier::ImportSpecifier(it) => Self::Named {
+                imported: it.imported.span().source_text(src),
+                local: &it.local,
+                type_only: it.import_kind.is_type(),
+            },
+        }
+    }
+
+    /// Sets whether a named specifier is written with `type`.
+    pub fn with_type_only(self, value: bool) -> Self {
+        match self {
+            Self::Named {
+                imported, local, ..
+            } => Self::Named {
+                imported,
+                local,
+                type_only: value,
+            },
+            it => it,
+        }
+    }
 
-        match left.to_lowercase().cmp(&right.to_lowercase()) {
-            Ordering::Equal => left.cmp(right),
-            ord => ord,
+    pub fn local(&self) -> &'a BindingIdentifier<'a> {
+        match self {
+            Self::Default(local) | Self::Namespace(local) => local,
+            Self::Named { local, .. } => local,
         }
     }
 }
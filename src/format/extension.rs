@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use crate::config::{RelativeExtensions, Rule};
+
+use super::{ImportElement, Matcher};
+
+const SCRIPT_EXTENSIONS: [&str; 8] = ["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+/// Extensions of the modules that are neither scripts nor assets, which are never rewritten.
+const DATA_EXTENSIONS: [&str; 3] = ["json", "wasm", "node"];
+
+/// Rewrites the extension of a relative module specifier to follow the policy.
+pub fn rewrite_extension<'a>(
+    element: &mut ImportElement<'a>,
+    policy: RelativeExtensions,
+    path: &Path,
+    matcher: &Matcher<'a>,
+) {
+    if policy == RelativeExtensions::Preserve || matcher.matches_rule(&Rule::Asset, element) {
+        return;
+    }
+
+    let module = element.module();
+
+    // Fragments are kept as is, and queries are assets anyway
+    if !module.starts_with('.') || module.contains(['?', '#']) {
+        return;
+    }
+
+    let rewritten = match policy {
+        RelativeExtensions::Preserve => None,
+        RelativeExtensions::Always => add_extension(module, path),
+        RelativeExtensions::Never => remove_extension(module),
+    };
+
+    if let Some(rewritten) = rewritten {
+        element.rewritten_module = Some(rewritten);
+    }
+}
+
+fn add_extension(module: &str, path: &Path) -> Option<String> {
+    // The extension a module without one is emitted with, assuming it is written in the same
+    // module format as the importing file
+    let default = match path.extension().and_then(|it| it.to_str()) {
+        Some("mts" | "mjs") => "mjs",
+        Some("cts" | "cjs") => "cjs",
+        _ => "js",
+    };
+
+    // Directories can only be told apart when written as `.`, `..` or with a trailing slash, and
+    // the others are taken as files, e.g. `./components` to `./components.js`
+    let name = module.rsplit('/').next().unwrap_or(module);
+    if matches!(name, "" | "." | "..") {
+        let directory = module.strip_suffix('/').unwrap_or(module);
+        return Some(format!("{directory}/index.{default}"));
+    }
+
+    let Some((stem, extension)) = name.rsplit_once('.').filter(|(stem, _)| !stem.is_empty()) else {
+        return Some(format!("{module}.{default}"));
+    };
+
+    let emitted = match extension {
+        "ts" | "tsx" => "js",
+        "mts" => "mjs",
+        "cts" => "cjs",
+        "js" | "jsx" | "mjs" | "cjs" => return None,
+        _ if DATA_EXTENSIONS.contains(&extension) => return None,
+        // Not an extension but part of the name, e.g. `./utils.server`
+        _ => return Some(format!("{module}.{default}")),
+    };
+
+    let directory = &module[..module.len() - name.len()];
+    Some(format!("{directory}{stem}.{emitted}"))
+}
+
+fn remove_extension(module: &str) -> Option<String> {
+    let name = module.rsplit('/').next().unwrap_or(module);
+
+    let (stem, extension) = name.rsplit_once('.')?;
+    if stem.is_empty() || !SCRIPT_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+
+    Some(module[..module.len() - extension.len() - 1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(module: &str, path: &str) -> Option<String> {
+        add_extension(module, Path::new(path))
+    }
+
+    #[test]
+    fn add_extensions() {
+        assert_eq!(
+            add("./utils", "src/index.ts").as_deref(),
+            Some("./utils.js")
+        );
+        assert_eq!(
+            add("./utils", "src/index.mts").as_deref(),
+            Some("./utils.mjs")
+        );
+        assert_eq!(
+            add("./utils", "src/index.cjs").as_deref(),
+            Some("./utils.cjs")
+        );
+        assert_eq!(
+            add("./utils.server", "src/index.ts").as_deref(),
+            Some("./utils.server.js")
+        );
+
+        assert_eq!(
+            add("./utils.ts", "src/index.ts").as_deref(),
+            Some("./utils.js")
+        );
+        assert_eq!(
+            add("./App.tsx", "src/index.ts").as_deref(),
+            Some("./App.js")
+        );
+        assert_eq!(
+            add("../lib/a.mts", "src/index.ts").as_deref(),
+            Some("../lib/a.mjs")
+        );
+        assert_eq!(add("./a.cts", "src/index.ts").as_deref(), Some("./a.cjs"));
+
+        assert_eq!(add("./utils.js", "src/index.ts"), None);
+        assert_eq!(add("./data.json", "src/index.ts"), None);
+    }
+
+    #[test]
+    fn add_index_modules() {
+        assert_eq!(add(".", "src/index.ts").as_deref(), Some("./index.js"));
+        assert_eq!(add("..", "src/index.ts").as_deref(), Some("../index.js"));
+        assert_eq!(
+            add("./lib/", "src/index.ts").as_deref(),
+            Some("./lib/index.js")
+        );
+        assert_eq!(
+            add("../../", "src/index.ts").as_deref(),
+            Some("../../index.js")
+        );
+        assert_eq!(
+            add("./lib/", "src/index.mts").as_deref(),
+            Some("./lib/index.mjs")
+        );
+        assert_eq!(add(".", "src/index.cts").as_deref(), Some("./index.cjs"));
+    }
+
+    #[test]
+    fn remove_extensions() {
+        assert_eq!(remove_extension("./utils.js").as_deref(), Some("./utils"));
+        assert_eq!(
+            remove_extension("../lib/a.mts").as_deref(),
+            Some("../lib/a")
+        );
+        assert_eq!(remove_extension("./data.json"), None);
+        assert_eq!(remove_extension("./utils"), None);
+        assert_eq!(remove_extension("./.js"), None);
+    }
+}
@@ -1,16 +1,18 @@
-use std::collections::LinkedList;
+use std::collections::{BTreeMap, LinkedList};
+use std::path::Path;
 
-use anyhow::{Ok, Result};
+use anyhow::{bail, Ok, Result};
 use oxc::ast::ast::Statement;
 use oxc::parser::ParserReturn;
 use oxc::span::{GetSpan, Span};
 use ropey::Rope;
 
-use crate::config::Configuration;
+use crate::config::{Configuration, UnmatchedImports};
 
 use super::{
-    insert, line_is_blank, remove_span, ChangedSpan, CommentElement, ImportElement, LineSpan,
-    Matcher, ModuleElement, ProgramParts,
+    apply_type_style, insert, line_is_blank, normalize, remove_span, rewrite_alias,
+    rewrite_builtin_prefix, rewrite_extension, ChangedSpan, CommentElement, ImportElement,
+    LineSpan, Matcher, ModuleElement, ProgramParts, UsageChecker,
 };
 
 pub struct FormatterReturn {
@@ -20,6 +22,7 @@ pub struct FormatterReturn {
 
 pub struct Formatter<'a> {
     pub config: &'a Configuration,
+    pub path: &'a Path,
     pub src: &'a str,
     pub rope: Rope,
     pub ast: ParserReturn<'a>,
@@ -44,20 +47,58 @@ impl<'a> Formatter<'a> {
             }
         }
 
-        let groups = self.organize(parts.imports);
+        let mut imports = parts.imports;
+        for element in imports.iter_mut() {
+            normalize(element, self.config.normalize, self.src);
+        }
+
+        if self.config.remove_unused {
+            imports = self.remove_unused(imports);
+        }
+
+        imports = apply_type_style(imports, self.config.type_import_style, self.src);
+
+        // Rewritten before grouping, so that the imports are matched as they are written out
+        let matcher = Matcher::new(self.config);
+        for element in imports.iter_mut() {
+            rewrite_builtin_prefix(element, self.config);
+            rewrite_alias(element, self.config, self.path);
+            rewrite_extension(
+                element,
+                self.config.relative_extensions,
+                self.path,
+                &matcher,
+            );
+        }
+
+        // The sub-groups split by `splitBy` are always separated from each other
+        let blocks: Vec<_> = self
+            .organize(imports)?
+            .into_iter()
+            .flat_map(|group| {
+                let last = group.len() - 1;
+                group
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, block)| (block, index < last))
+            })
+            .collect();
 
         // Insert imports after preamable and before those previously inserted
         let pos = self.rope.byte_to_char(parts.preamable.end as usize);
         let mut inserted = ChangedSpan::empty(parts.preamable.end);
-        for group in groups.iter().rev() {
+        for (index, (group, split)) in blocks.iter().enumerate().rev() {
+            // Only separate the last group from the code following the imports if not required
+            let separate = self.config.separate_groups || *split || index + 1 == blocks.len();
+
             let line = output.char_to_line(pos);
-            if !line_is_blank(&output, line) {
+            if separate && !line_is_blank(&output, line) {
                 inserted.len += insert(&mut output, pos, "\n");
             }
 
             for element in group.iter().rev() {
                 inserted.len += insert(&mut output, pos, "\n");
-                inserted.len += insert(&mut output, pos, element.span.source_text(self.src));
+                inserted.len += insert(&mut output, pos, &element.render(self.src));
                 inserted.len += insert(&mut output, pos, &indent);
 
                 for comment in element.comments.iter().rev() {
@@ -117,11 +158,10 @@ impl<'a> Formatter<'a> {
                 Statement::ImportDeclaration(decl) => {
                     let comments = self.pull_related_comments(&mut comments_before, statement);
 
-                    parts.imports.push_back(ImportElement {
-                        span,
-                        comments,
-                        decl,
-                    });
+                    let inner_comments = !self.get_comments(span.start, span.end).is_empty();
+                    let element =
+                        ImportElement::from_ast(self.src, span, comments, inner_comments, decl);
+                    parts.imports.push_back(element);
                 }
                 Statement::TSModuleDeclaration(decl) => {
                     if let Some(element) = ModuleElement::from_ast(decl) {
@@ -139,30 +179,119 @@ impl<'a> Formatter<'a> {
         parts
     }
 
-    fn organize(&self, mut imports: LinkedList<ImportElement<'a>>) -> Vec<Vec<ImportElement<'a>>> {
-        let mut groups = Vec::with_capacity(self.config.groups.len() + 1);
+    fn remove_unused(
+        &'a self,
+        imports: LinkedList<ImportElement<'a>>,
+    ) -> LinkedList<ImportElement<'a>> {
+        let checker =
+            UsageChecker::new(self.config, self.src, &self.ast.program, &self.ast.trivias);
+
+        imports
+            .into_iter()
+            .filter_map(|mut element| {
+                element.retain_specifiers(|it| checker.is_used(it));
+
+                let emptied =
+                    element.modified && element.specifiers.as_ref().is_some_and(Vec::is_empty);
+                if !emptied {
+                    return Some(element);
+                }
+
+                // Type imports have no side effects to keep
+                if self.config.keep_side_effects && !element.type_only {
+                    element.specifiers = None;
+                    return Some(element);
+                }
+
+                None
+            })
+            .collect()
+    }
+
+    /// Sorts the imports into groups, each made of the blocks of its sub-groups.
+    fn organize(
+        &self,
+        imports: LinkedList<ImportElement<'a>>,
+    ) -> Result<Vec<Vec<Vec<ImportElement<'a>>>>> {
+        let mut groups: Vec<Vec<_>> = self.config.groups.iter().map(|_| Vec::new()).collect();
+        let mut unmatched = Vec::new();
 
         let matcher = Matcher::new(self.config);
 
-        for group in &self.config.groups {
-            let matched: Vec<_> = imports
-                .extract_if(|element| matcher.matches_group(group, element))
-                .collect();
+        for element in imports {
+            match matcher.find_group(&element) {
+                Some(index) => groups[index].push(element),
+                None => unmatched.push(element),
+            }
+        }
 
-            if !matched.is_empty() {
-                groups.push(matched);
+        if let Some(element) = unmatched.first() {
+            let index = self
+                .config
+                .groups
+                .iter()
+                .position(|group| group.includes_unmatched());
+
+            match index {
+                Some(index) => groups[index].append(&mut unmatched),
+                None if self.config.unmatched == UnmatchedImports::Error => {
+                    bail!(
+                        "import of \"{}\" does not match any group",
+                        element.module()
+                    );
+                }
+                None => groups.push(unmatched),
             }
         }
 
-        if !imports.is_empty() {
-            groups.push(imports.into_iter().collect());
+        let mut organized = Vec::with_capacity(groups.len());
+
+        for (index, mut group) in groups.into_iter().enumerate() {
+            let config = self.config.groups.get(index);
+
+            let order = config.map(|it| &it.order[..]).unwrap_or_default();
+            let sort_order = config
+                .and_then(|it| it.sort_order)
+                .unwrap_or(self.config.sort_order);
+            group.sort_by(|a, b| {
+                let a_rank = matcher.order_rank(order, a);
+                let b_rank = matcher.order_rank(order, b);
+                a_rank
+                    .cmp(&b_rank)
+                    .then_with(|| a.compare(b, sort_order, self.config.builtin_prefix))
+            });
+
+            match config.and_then(|it| it.split_by.as_ref()) {
+                Some(split_by) => {
+                    let mut split = BTreeMap::<_, Vec<_>>::new();
+                    for element in group {
+                        let key = split_by.key(element.module());
+                        split.entry(key).or_default().push(element);
+                    }
+
+                    // Sub-groups with pinned imports are moved like the pinned imports, and the
+                    // others stay in the order of their keys
+                    let mut split: Vec<_> = split
+                        .into_values()
+                        .map(|block| {
+                            let rank = block.iter().map(|it| matcher.order_rank(order, it)).min();
+                            (rank, block)
+                        })
+                        .collect();
+                    split.sort_by_key(|(rank, _)| *rank);
+
+                    organized.push(split.into_iter().map(|(_, block)| block).collect());
+                }
+                None => organized.push(vec![group]),
+            }
         }
 
-        for group in groups.iter_mut() {
-            group.sort_by(|a, b| a.compare(b));
+        for group in organized.iter_mut() {
+            group.retain(|block| !block.is_empty());
         }
+        organized.retain(|group| !group.is_empty());
 
-        groups
+        Ok(organized)
     }
 
     fn get_preamable_span(&self) -> Span {
@@ -218,3 +347,35 @@ impl<'a> Formatter<'a> {
         comments.split_off(split_at)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::format::format_with_config;
+
+    #[test]
+    fn separate_sub_groups() {
+        let config = json!({
+            "groups": [
+                { "include": ["<npm>"] },
+                { "include": ["<relative>"], "splitBy": "directory" },
+            ],
+            "separateGroups": false,
+        });
+
+        let src = r#"import a from "./hooks/a";
+import c from "./c";
+import React from "react";
+import b from "./components/b";
+"#;
+        let expected = r#"import React from "react";
+import c from "./c";
+
+import b from "./components/b";
+
+import a from "./hooks/a";
+"#;
+        assert_eq!(format_with_config(config, "src/index.ts", src), expected);
+    }
+}
@@ -1,10 +1,16 @@
-use std::collections::HashSet;
-use std::sync::LazyLock;
+use std::borrow::Cow;
+use std::cmp::Reverse;
 
-use crate::config::{Configuration, CustomRule, ImportGroup, Rule, RuleSet};
+use oxc::ast::ast::ImportAttributeKey;
+
+use crate::config::{
+    Configuration, CustomRule, ImportGroup, MatchStrategy, OrderEntry, PackageJson, Rule, RuleSet,
+    Specificity,
+};
 use crate::re;
+use crate::utils::package_name;
 
-use super::ImportElement;
+use super::{is_builtin, ImportElement};
 
 pub struct Matcher<'a> {
     config: &'a Configuration,
@@ -15,6 +21,47 @@ impl<'a> Matcher<'a> {
         Self { config }
     }
 
+    /// Finds the group to place the import in, according to the match strategy.
+    pub fn find_group(&self, element: &ImportElement<'a>) -> Option<usize> {
+        let groups = &self.config.groups;
+
+        match self.config.match_strategy {
+            MatchStrategy::First => groups
+                .iter()
+                .position(|group| self.matches_group(group, element)),
+            MatchStrategy::Specificity => groups
+                .iter()
+                .enumerate()
+                .filter_map(|(index, group)| {
+                    let specificity = self.match_specificity(group, element)?;
+                    Some((specificity, Reverse(index)))
+                })
+                .max()
+                .map(|(_, Reverse(index))| index),
+        }
+    }
+
+    /// Ranks the import by the first matching entry of the `order` of a group. Imports pinned before
+    /// the `"..."` placeholder come first, then the ones not pinned, then the ones pinned after it.
+    pub fn order_rank(&self, order: &[OrderEntry], element: &ImportElement<'a>) -> (u8, usize) {
+        let rest = order
+            .iter()
+            .position(|entry| matches!(entry, OrderEntry::Rest))
+            .unwrap_or(order.len());
+
+        for (index, entry) in order.iter().enumerate() {
+            let OrderEntry::Rule(rule) = entry else {
+                continue;
+            };
+
+            if self.matches_rule(rule, element) {
+                return if index < rest { (0, index) } else { (2, index) };
+            }
+        }
+
+        (1, 0)
+    }
+
     pub fn matches_group(&self, group: &ImportGroup, element: &ImportElement<'a>) -> bool {
         if !self.matches_rules(&group.include, element) {
             return false;
@@ -38,99 +85,89 @@ impl<'a> Matcher<'a> {
             Rule::Effect => self.matches_effect(element),
             Rule::Builtin => self.matches_builtin(element),
             Rule::Relative => self.matches_relative(element),
+            Rule::Parent => relative_kind(element.module()) == Some(RelativeKind::Parent),
+            Rule::Sibling => relative_kind(element.module()) == Some(RelativeKind::Sibling),
+            Rule::Index => relative_kind(element.module()) == Some(RelativeKind::Index),
+            Rule::Subpath => self.matches_subpath(element),
             Rule::Alias => self.matches_alias(element),
             Rule::Npm => self.matches_npm(element),
+            Rule::Dependency => self.matches_package(element, PackageJson::is_dependency),
+            Rule::DevDependency => self.matches_package(element, PackageJson::is_dev_dependency),
+            Rule::PeerDependency => self.matches_package(element, PackageJson::is_peer_dependency),
+            Rule::SelfPackage => self.matches_package(element, PackageJson::is_self),
+            Rule::Undeclared => self.matches_undeclared(element),
+            Rule::Asset => self.matches_asset(element),
+            Rule::Default => self.matches_default(element),
+            Rule::Namespace => self.matches_namespace(element),
+            Rule::Named => self.matches_named(element),
+            Rule::Mixed => self.matches_mixed(element),
+            Rule::Type => element.type_only,
+            Rule::With { key, value } => self.matches_with(key, value.as_deref(), element),
+            // Handled by the formatter after all groups are matched
+            Rule::Unmatched => false,
             Rule::Custom(custom) => self.matches_custom(custom, element),
+            Rule::All(rules) => rules.iter().all(|rule| self.matches_rule(rule, element)),
+            Rule::Any(rules) => self.matches_rules(rules, element),
+            Rule::Not(rule) => !self.matches_rule(rule, element),
+        }
+    }
+
+    fn match_specificity(
+        &self,
+        group: &ImportGroup,
+        element: &ImportElement<'a>,
+    ) -> Option<Specificity> {
+        if self.matches_rules(&group.exclude, element) {
+            return None;
+        }
+
+        group
+            .include
+            .iter()
+            .filter_map(|rule| self.rule_specificity(rule, element))
+            .max()
+    }
+
+    fn rule_specificity(&self, rule: &Rule, element: &ImportElement<'a>) -> Option<Specificity> {
+        match rule {
+            Rule::All(rules) => rules
+                .iter()
+                .map(|rule| self.rule_specificity(rule, element))
+                .try_fold(Specificity::Unmatched, |max, it| Some(max.max(it?))),
+            Rule::Any(rules) => rules
+                .iter()
+                .filter_map(|rule| self.rule_specificity(rule, element))
+                .max(),
+            _ => self.matches_rule(rule, element).then(|| rule.specificity()),
         }
     }
 
     fn matches_effect(&self, element: &ImportElement<'a>) -> bool {
-        element.decl.specifiers.is_none()
+        element.specifiers.is_none()
     }
 
     fn matches_builtin(&self, element: &ImportElement<'a>) -> bool {
-        // node -e 'console.log(JSON.stringify(require("node:module").builtinModules))'
-        static BUILTINS: LazyLock<HashSet<&str>> = LazyLock::new(|| {
-            HashSet::from([
-                "_http_agent",
-                "_http_client",
-                "_http_common",
-                "_http_incoming",
-                "_http_outgoing",
-                "_http_server",
-                "_stream_duplex",
-                "_stream_passthrough",
-                "_stream_readable",
-                "_stream_transform",
-                "_stream_wrap",
-                "_stream_writable",
-                "_tls_common",
-                "_tls_wrap",
-                "assert",
-                "assert/strict",
-                "async_hooks",
-                "buffer",
-                "child_process",
-                "cluster",
-                "console",
-                "constants",
-                "crypto",
-                "dgram",
-                "diagnostics_channel",
-                "dns",
-                "dns/promises",
-                "domain",
-                "events",
-                "fs",
-                "fs/promises",
-                "http",
-                "http2",
-                "https",
-                "inspector",
-                "inspector/promises",
-                "module",
-                "net",
-                "os",
-                "path",
-                "path/posix",
-                "path/win32",
-                "perf_hooks",
-                "process",
-                "punycode",
-                "querystring",
-                "readline",
-                "readline/promises",
-                "repl",
-                "stream",
-                "stream/consumers",
-                "stream/promises",
-                "stream/web",
-                "string_decoder",
-                "sys",
-                "timers",
-                "timers/promises",
-                "tls",
-                "trace_events",
-                "tty",
-                "url",
-                "util",
-                "util/types",
-                "v8",
-                "vm",
-                "wasi",
-                "worker_threads",
-                "zlib",
-            ])
-        });
-
-        let module = element.module();
-        module.starts_with("node:") || BUILTINS.contains(&module)
+        is_builtin(element.module())
     }
 
     fn matches_relative(&self, element: &ImportElement<'a>) -> bool {
         element.module().starts_with('.')
     }
 
+    fn matches_subpath(&self, element: &ImportElement<'a>) -> bool {
+        element.module().starts_with('#')
+    }
+
+    /// Resolves a subpath import like `#db` with the `imports` of `packageJson`.
+    fn subpath_target(&self, element: &ImportElement<'a>) -> Option<String> {
+        if !self.matches_subpath(element) {
+            return None;
+        }
+
+        let package_json = self.config.package_json.as_ref()?;
+        package_json.resolve_import(element.module())
+    }
+
     fn matches_alias(&self, element: &ImportElement<'a>) -> bool {
         let module = element.module();
 
@@ -140,18 +177,199 @@ impl<'a> Matcher<'a> {
             }
         }
 
-        false
+        if let Some(alias_paths) = &self.config.alias_paths {
+            if alias_paths.matches(module) {
+                return true;
+            }
+        }
+
+        // Subpath imports of the package's own files are internal like aliases
+        if self
+            .subpath_target(element)
+            .is_some_and(|target| target.starts_with("./"))
+        {
+            return true;
+        }
+
+        self.import_map_external(element) == Some(false)
+    }
+
+    /// Whether the import map maps the import to a package or a remote module, or `None` if it is
+    /// not in the import map.
+    fn import_map_external(&self, element: &ImportElement<'a>) -> Option<bool> {
+        let import_map = self.config.import_map.as_ref()?;
+        import_map.is_external(element.module())
     }
 
     fn matches_npm(&self, element: &ImportElement<'a>) -> bool {
+        self.package(element).is_some()
+    }
+
+    /// The package name of an npm import, e.g. `@scope/pkg` for `@scope/pkg/sub/path`. Subpath
+    /// imports mapped to a package are resolved to that package.
+    fn package<'e>(&self, element: &'e ImportElement<'a>) -> Option<Cow<'e, str>> {
         if self.matches_alias(element) {
+            return None;
+        }
+
+        // Import map keys like `$std/` are not package names, but are external anyway
+        if self.import_map_external(element) == Some(true) {
+            return Some(Cow::Borrowed(package_name(element.module())));
+        }
+
+        let module = match self.subpath_target(element) {
+            Some(target) => Cow::Owned(target),
+            None => Cow::Borrowed(element.module()),
+        };
+
+        if !re!(r"^@?[0-9A-Za-z\-]").is_match(&module) {
+            return None;
+        }
+
+        Some(match module {
+            Cow::Borrowed(module) => Cow::Borrowed(package_name(module)),
+            Cow::Owned(module) => Cow::Owned(package_name(&module).to_string()),
+        })
+    }
+
+    fn matches_package(
+        &self,
+        element: &ImportElement<'a>,
+        predicate: fn(&PackageJson, &str) -> bool,
+    ) -> bool {
+        let Some(package_json) = &self.config.package_json else {
+            return false;
+        };
+
+        self.package(element)
+            .is_some_and(|package| predicate(package_json, &package))
+    }
+
+    fn matches_undeclared(&self, element: &ImportElement<'a>) -> bool {
+        let Some(package_json) = &self.config.package_json else {
             return false;
+        };
+
+        // Builtins are never declared, unless a package of the same name is
+        self.package(element).is_some_and(|package| {
+            !package_json.is_declared(&package) && !self.matches_builtin(element)
+        })
+    }
+
+    fn matches_asset(&self, element: &ImportElement<'a>) -> bool {
+        // Bundler queries like `?url`, `?raw` or `?worker` always load the module as an asset
+        let module = element.module();
+        if module.contains('?') {
+            return true;
         }
 
-        re!(r"^@?[0-9A-Za-z\-]").is_match(element.module())
+        let filename = module.rsplit('/').next().unwrap_or(module);
+        let Some((stem, extension)) = filename.rsplit_once('.') else {
+            return false;
+        };
+
+        if stem.is_empty() || extension.is_empty() {
+            return false;
+        }
+
+        self.config
+            .asset_extensions
+            .iter()
+            .any(|it| it.trim_start_matches('.').eq_ignore_ascii_case(extension))
+    }
+
+    fn matches_default(&self, element: &ImportElement<'a>) -> bool {
+        let bindings = element.bindings();
+        bindings.default && !bindings.namespace && !bindings.named
+    }
+
+    fn matches_namespace(&self, element: &ImportElement<'a>) -> bool {
+        let bindings = element.bindings();
+        bindings.namespace && !bindings.default
+    }
+
+    fn matches_named(&self, element: &ImportElement<'a>) -> bool {
+        let bindings = element.bindings();
+        bindings.named && !bindings.default
+    }
+
+    fn matches_mixed(&self, element: &ImportElement<'a>) -> bool {
+        let bindings = element.bindings();
+        bindings.default && (bindings.namespace || bindings.named)
+    }
+
+    fn matches_with(&self, key: &str, value: Option<&str>, element: &ImportElement<'a>) -> bool {
+        // The deprecated `assert { ... }` form is parsed into the same clause
+        let Some(clause) = &element.decl.with_clause else {
+            return false;
+        };
+
+        clause.with_entries.iter().any(|attribute| {
+            let name = match &attribute.key {
+                ImportAttributeKey::Identifier(it) => it.name.as_str(),
+                ImportAttributeKey::StringLiteral(it) => it.value.as_str(),
+            };
+
+            name == key && value.is_none_or(|value| attribute.value.value.as_str() == value)
+        })
     }
 
     fn matches_custom(&self, custom: &CustomRule, element: &ImportElement<'a>) -> bool {
         custom.matches(element.module())
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RelativeKind {
+    /// Modules in the parent directories, e.g. `../utils`.
+    Parent,
+    /// Modules in the same directory, e.g. `./utils`.
+    Sibling,
+    /// The index module of the same directory, e.g. `.`, `./` or `./index.js`.
+    Index,
+}
+
+/// Classifies a relative module after normalizing its path, so that `./a/../b` is a sibling and
+/// `./a/..` is the index.
+fn relative_kind(module: &str) -> Option<RelativeKind> {
+    if !module.starts_with('.') {
+        return None;
+    }
+
+    let path = module.split(['?', '#']).next().unwrap_or(module);
+
+    let mut parents = 0;
+    let mut segments = Vec::new();
+
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                if segments.pop().is_none() {
+                    parents += 1;
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    if parents > 0 {
+        return Some(RelativeKind::Parent);
+    }
+
+    let index = match segments.as_slice() {
+        [] => true,
+        [name] => {
+            *name == "index"
+                || name
+                    .strip_prefix("index.")
+                    .is_some_and(|extension| !extension.is_empty() && !extension.contains('.'))
+        }
+        _ => false,
+    };
+
+    match index {
+        true => Some(RelativeKind::Index),
+        false => Some(RelativeKind::Sibling),
+    }
+}
@@ -8,19 +8,31 @@ use ropey::Rope;
 
 use crate::config::Configuration;
 
+mod alias;
+mod builtin;
 mod editing;
 mod element;
+mod extension;
 mod formatter;
 mod line;
 mod matcher;
+mod normalize;
 mod program;
+mod type_style;
+mod unused;
 
+use alias::*;
+use builtin::*;
 use editing::*;
 use element::*;
+use extension::*;
 use formatter::*;
 use line::*;
 use matcher::*;
+use normalize::*;
 use program::*;
+use type_style::*;
+use unused::*;
 
 pub fn format_source(config: &Configuration, path: &Path, src: &str) -> Result<Rope> {
     let rope = Rope::from_str(src);
@@ -35,6 +47,7 @@ pub fn format_source(config: &Configuration, path: &Path, src: &str) -> Result<R
 
     let formatter = Formatter {
         config,
+        path,
         src,
         rope,
         ast,
@@ -54,3 +67,19 @@ pub fn format_source(config: &Configuration, path: &Path, src: &str) -> Result<R
 
     Ok(ret.output)
 }
+
+/// Formats the source as the plugin does, with the config written in JSON.
+#[cfg(test)]
+pub fn format_with_config(config: serde_json::Value, path: &str, src: &str) -> String {
+    use dprint_core::configuration::ConfigKeyMap;
+
+    use crate::config::resolve_config;
+
+    let mut raw: ConfigKeyMap = serde_json::from_value(config).unwrap();
+    let config = resolve_config(&mut raw, &mut vec![]).unwrap();
+    let path = Path::new(path);
+
+    format_source(&config.resolve_for(path), path, src)
+        .unwrap()
+        .to_string()
+}
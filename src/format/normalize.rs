@@ -0,0 +1,160 @@
+use crate::config::Normalize;
+
+use super::{ImportElement, SpecifierElement};
+
+/// Rewrites the degenerate forms of an import declaration enabled in the config.
+pub fn normalize(element: &mut ImportElement<'_>, options: Normalize, src: &str) {
+    if element.inner_comments {
+        return;
+    }
+
+    if options.empty_braces {
+        remove_empty_braces(element, src);
+    }
+
+    if options.default_specifier {
+        promote_default_specifier(element);
+    }
+
+    if options.duplicate_specifiers {
+        remove_duplicate_specifiers(element);
+    }
+}
+
+/// `import {} from "x"` to `import "x"`, and `import X, {} from "x"` to `import X from "x"`.
+fn remove_empty_braces(element: &mut ImportElement<'_>, src: &str) {
+    // Type imports have no side effects, so `import type {} from "x"` is left as is
+    if element.type_only {
+        return;
+    }
+
+    let Some(specifiers) = &element.specifiers else {
+        return;
+    };
+
+    if specifiers
+        .iter()
+        .any(|it| matches!(it, SpecifierElement::Named { .. }))
+    {
+        return;
+    }
+
+    // Braces are not in the AST, so look for them in the text before the source, which has no
+    // comments as the imports with comments inside are not normalized
+    let head = &src[element.decl.span.start as usize..element.decl.source.span.start as usize];
+    if !head.contains('{') {
+        return;
+    }
+
+    if specifiers.is_empty() {
+        element.specifiers = None;
+    }
+
+    element.modified = true;
+}
+
+/// `import { default as X } from "x"` to `import X from "x"`.
+fn promote_default_specifier(element: &mut ImportElement<'_>) {
+    let Some(specifiers) = &mut element.specifiers else {
+        return;
+    };
+
+    if specifiers
+        .iter()
+        .any(|it| matches!(it, SpecifierElement::Default(_)))
+    {
+        return;
+    }
+
+    // `{ type default as X }` has no equivalent default import unless the whole import is a type
+    let position = specifiers.iter().position(|it| match it {
+        SpecifierElement::Named {
+            imported,
+            type_only,
+            ..
+        } => !type_only && matches!(*imported, "default" | "\"default\"" | "'default'"),
+        _ => false,
+    });
+
+    let Some(position) = position else {
+        return;
+    };
+
+    let local = specifiers.remove(position).local();
+    specifiers.insert(0, SpecifierElement::Default(local));
+    element.modified = true;
+}
+
+/// `import { a, a as a } from "x"` to `import { a } from "x"`. Of a type-only and a value duplicate,
+/// the value one is kept.
+fn remove_duplicate_specifiers(element: &mut ImportElement<'_>) {
+    let Some(specifiers) = &mut element.specifiers else {
+        return;
+    };
+
+    let mut kept: Vec<SpecifierElement> = Vec::with_capacity(specifiers.len());
+
+    for specifier in specifiers.drain(..) {
+        let SpecifierElement::Named {
+            imported,
+            local,
+            type_only,
+        } = &specifier
+        else {
+            kept.push(specifier);
+            continue;
+        };
+
+        let duplicate = kept.iter_mut().find_map(|it| match it {
+            SpecifierElement::Named {
+                imported: kept_imported,
+                local: kept_local,
+                type_only,
+            } if *kept_imported == *imported && kept_local.name == local.name => Some(type_only),
+            _ => None,
+        });
+
+        match duplicate {
+            Some(kept_type_only) => {
+                *kept_type_only &= *type_only;
+                element.modified = true;
+            }
+            None => kept.push(specifier),
+        }
+    }
+
+    *specifiers = kept;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::format::format_with_config;
+
+    fn format(src: &str) -> String {
+        let config = json!({ "normalize": { "emptyBraces": true } });
+        format_with_config(config, "src/index.ts", src)
+    }
+
+    #[test]
+    fn remove_empty_braces() {
+        assert_eq!(format("import {} from \"x\";\n"), "import \"x\";\n");
+        assert_eq!(
+            format("import X, {} from \"x\";\n"),
+            "import X from \"x\";\n"
+        );
+    }
+
+    #[test]
+    fn keep_imports_with_comments() {
+        let src = "import X, /* c */ {} from \"x\";\n";
+        assert_eq!(format(src), src);
+
+        let src = "import X, {\n  // no bindings yet\n} from \"x\";\n";
+        assert_eq!(format(src), src);
+
+        let src = "import /* { */ X from \"x\";\n";
+        assert_eq!(format(src), src);
+    }
+}
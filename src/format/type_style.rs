@@ -0,0 +1,246 @@
+use std::collections::LinkedList;
+
+use crate::config::TypeImportStyle;
+
+use super::{ImportElement, SpecifierElement};
+
+/// Converts the type-only specifiers between `import { type A, b } from "x"` and separate
+/// `import type { A } from "x"` declarations.
+pub fn apply_type_style<'a>(
+    imports: LinkedList<ImportElement<'a>>,
+    style: TypeImportStyle,
+    src: &'a str,
+) -> LinkedList<ImportElement<'a>> {
+    match style {
+        TypeImportStyle::Preserve => imports,
+        TypeImportStyle::Inline => inline_type_imports(imports, src),
+        TypeImportStyle::Separate => separate_type_imports(imports),
+    }
+}
+
+fn inline_type_imports<'a>(
+    imports: LinkedList<ImportElement<'a>>,
+    src: &'a str,
+) -> LinkedList<ImportElement<'a>> {
+    let mut imports: Vec<_> = imports.into_iter().map(Some).collect();
+
+    for index in 0..imports.len() {
+        let Some(element) = &imports[index] else {
+            continue;
+        };
+
+        if !can_inline(element) {
+            continue;
+        }
+
+        let target = imports.iter().position(|it| {
+            it.as_ref()
+                .is_some_and(|target| accepts_inline(target, element, src))
+        });
+
+        // A type import with no value import to merge into is kept, so that it is still elided
+        let Some(target) = target else {
+            continue;
+        };
+
+        let element = imports[index].take().unwrap();
+        let target = imports[target].as_mut().unwrap();
+
+        let specifiers = element.specifiers.into_iter().flatten();
+        target
+            .specifiers
+            .get_or_insert_with(Vec::new)
+            .extend(specifiers.map(|it| it.with_type_only(true)));
+        target.comments.extend(element.comments);
+        target.modified = true;
+    }
+
+    imports.into_iter().flatten().collect()
+}
+
+/// Whether the import is `import type { ... }` with named specifiers only, as `import type X` and
+/// `import type * as X` can not be written inline.
+fn can_inline(element: &ImportElement<'_>) -> bool {
+    let Some(specifiers) = &element.specifiers else {
+        return false;
+    };
+
+    element.type_only
+        && !element.inner_comments
+        && !specifiers.is_empty()
+        && specifiers
+            .iter()
+            .all(|it| matches!(it, SpecifierElement::Named { .. }))
+}
+
+fn accepts_inline(target: &ImportElement<'_>, element: &ImportElement<'_>, src: &str) -> bool {
+    let Some(specifiers) = &target.specifiers else {
+        return false;
+    };
+
+    // Named specifiers can not follow a namespace import
+    if specifiers
+        .iter()
+        .any(|it| matches!(it, SpecifierElement::Namespace(_)))
+    {
+        return false;
+    }
+
+    let with_clause = |it: &ImportElement<'_>| {
+        it.decl
+            .with_clause
+            .as_ref()
+            .map(|clause| clause.span.source_text(src))
+    };
+
+    !target.type_only
+        && !target.inner_comments
+        && target.module() == element.module()
+        && with_clause(target) == with_clause(element)
+}
+
+fn separate_type_imports(imports: LinkedList<ImportElement<'_>>) -> LinkedList<ImportElement<'_>> {
+    let is_inline_type = |it: &SpecifierElement| {
+        matches!(
+            it,
+            SpecifierElement::Named {
+                type_only: true,
+                ..
+            }
+        )
+    };
+
+    let mut result = LinkedList::new();
+
+    for mut element in imports {
+        let has_inline_types = element.specifiers.iter().flatten().any(is_inline_type);
+        if element.type_only || element.inner_comments || !has_inline_types {
+            result.push_back(element);
+            continue;
+        }
+
+        let specifiers = element.specifiers.take().unwrap_or_default();
+        let (types, values): (Vec<_>, Vec<_>) = specifiers.into_iter().partition(is_inline_type);
+
+        let types: Vec<_> = types
+            .into_iter()
+            .map(|it| it.with_type_only(false))
+            .collect();
+
+        // All bindings are types, so the whole declaration becomes a type import
+        if values.is_empty() {
+            element.specifiers = Some(types);
+            element.type_only = true;
+            element.modified = true;
+            result.push_back(element);
+            continue;
+        }
+
+        let split = ImportElement {
+            span: element.span,
+            comments: vec![],
+            decl: element.decl,
+            specifiers: Some(types),
+            type_only: true,
+            inner_comments: false,
+            rewritten_module: element.rewritten_module.clone(),
+            modified: true,
+        };
+
+        element.specifiers = Some(values);
+        element.modified = true;
+
+        result.push_back(element);
+        result.push_back(split);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::format::format_with_config;
+
+    fn format(style: &str, src: &str) -> String {
+        let config =
+            json!({ "groups": [{ "include": ["<unmatched>"] }], "typeImportStyle": style });
+        format_with_config(config, "src/index.ts", src)
+    }
+
+    #[test]
+    fn inline_into_value_imports() {
+        let src = r#"import { a } from "x";
+import type { A } from "x";
+"#;
+        assert_eq!(format("inline", src), "import { a, type A } from \"x\";\n");
+
+        let src = r#"import X from "x";
+import type { A, B as C } from "x";
+"#;
+        assert_eq!(
+            format("inline", src),
+            "import X, { type A, type B as C } from \"x\";\n"
+        );
+    }
+
+    #[test]
+    fn keep_type_imports_not_inlined() {
+        // Named specifiers can not follow a namespace import
+        let src = r#"import * as x from "x";
+import type { A } from "x";
+"#;
+        assert_eq!(format("inline", src), src);
+
+        // No value import to merge into
+        let src = "import type { A } from \"x\";\n";
+        assert_eq!(format("inline", src), src);
+
+        // Default and namespace type imports can not be written inline
+        let src = r#"import type X from "x";
+import { a } from "x";
+"#;
+        assert_eq!(format("inline", src), src);
+    }
+
+    #[test]
+    fn inline_with_same_attributes() {
+        let src = r#"import { a } from "./a.json" with { type: "json" };
+import type { A } from "./a.json";
+"#;
+        assert_eq!(format("inline", src), src);
+
+        let src = r#"import { a } from "./a.json" with { type: "json" };
+import type { A } from "./a.json" with { type: "json" };
+"#;
+        assert_eq!(
+            format("inline", src),
+            "import { a, type A } from \"./a.json\" with { type: \"json\" };\n"
+        );
+    }
+
+    #[test]
+    fn separate_type_specifiers() {
+        let src = "import { type A, b, type C } from \"x\";\n";
+        let expected = r#"import { b } from "x";
+import type { A, C } from "x";
+"#;
+        assert_eq!(format("separate", src), expected);
+
+        let src = "import X, { type A } from \"x\";\n";
+        let expected = r#"import X from "x";
+import type { A } from "x";
+"#;
+        assert_eq!(format("separate", src), expected);
+    }
+
+    #[test]
+    fn separate_all_types() {
+        let src = "import { type A, type B } from \"x\";\n";
+        assert_eq!(
+            format("separate", src),
+            "import type { A, B } from \"x\";\n"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use oxc::ast::ast::Program;
+use oxc::ast::Trivias;
+use oxc::semantic::{Semantic, SemanticBuilder};
+
+use crate::config::Configuration;
+use crate::re;
+
+use super::SpecifierElement;
+
+/// Finds the imported bindings never referenced in the file, including the references in types and
+/// JSX elements.
+pub struct UsageChecker<'a> {
+    semantic: Semantic<'a>,
+    keep: HashSet<&'a str>,
+}
+
+impl<'a> UsageChecker<'a> {
+    pub fn new(
+        config: &'a Configuration,
+        src: &'a str,
+        program: &'a Program<'a>,
+        trivias: &Trivias,
+    ) -> Self {
+        let semantic = SemanticBuilder::new(src).build(program).semantic;
+
+        let mut keep: HashSet<_> = config.keep_unused.iter().map(String::as_str).collect();
+
+        // The bindings used by the classic JSX runtime, e.g. `h` for `/** @jsx h */`
+        for comment in trivias.comments() {
+            let text = comment.span.source_text(src);
+            for captures in re!(r"@jsx(?:Frag)?\s+([A-Za-z_$][\w$]*)").captures_iter(text) {
+                keep.insert(captures.get(1).unwrap().as_str());
+            }
+        }
+
+        Self { semantic, keep }
+    }
+
+    pub fn is_used(&self, specifier: &SpecifierElement<'a>) -> bool {
+        let local = specifier.local();
+
+        if self.keep.contains(local.name.as_str()) {
+            return true;
+        }
+
+        // Bindings not analyzed are kept to be safe
+        let Some(symbol_id) = local.symbol_id.get() else {
+            return true;
+        };
+
+        let symbols = self.semantic.symbols();
+        !symbols.get_resolved_reference_ids(symbol_id).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::format::format_with_config;
+
+    fn format(src: &str) -> String {
+        format_with(json!({ "removeUnused": true }), src)
+    }
+
+    fn format_with(config: Value, src: &str) -> String {
+        format_with_config(config, "src/index.tsx", src)
+    }
+
+    #[test]
+    fn keep_type_references() {
+        let src = r#"import { A, B } from "x";
+
+let a: A;
+"#;
+        let expected = r#"import { A } from "x";
+
+let a: A;
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn keep_jsx_references() {
+        let src = r#"import Button from "./Button";
+import Link from "./Link";
+
+export const App = () => <Button />;
+"#;
+        let expected = r#"import Button from "./Button";
+
+export const App = () => <Button />;
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn keep_exports() {
+        let src = r#"import { a, b } from "x";
+
+export { a };
+"#;
+        let expected = r#"import { a } from "x";
+
+export { a };
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn keep_jsx_pragmas() {
+        let src = r#"/** @jsx h */
+import { h, Fragment } from "preact";
+import React from "react";
+
+export const App = () => <div />;
+"#;
+        let expected = r#"/** @jsx h */
+import { h } from "preact";
+import React from "react";
+
+export const App = () => <div />;
+"#;
+        assert_eq!(format(src), expected);
+
+        // `React` is only kept by default
+        let config = json!({ "removeUnused": true, "keepUnused": [] });
+        let expected = r#"/** @jsx h */
+import { h } from "preact";
+
+export const App = () => <div />;
+"#;
+        assert_eq!(format_with(config, src), expected);
+    }
+
+    #[test]
+    fn keep_side_effects() {
+        let src = r#"import { run } from "./run";
+import { a } from "./setup";
+import type { B } from "./types";
+
+run();
+"#;
+        let expected = r#"import { run } from "./run";
+
+run();
+"#;
+        assert_eq!(format(src), expected);
+
+        // Type imports have no side effects to keep
+        let config = json!({ "removeUnused": true, "keepSideEffects": true });
+        let expected = r#"import "./setup";
+
+import { run } from "./run";
+
+run();
+"#;
+        assert_eq!(format_with(config, src), expected);
+    }
+
+    #[test]
+    fn keep_imports_with_comments() {
+        let src = r#"import {
+  // used for config loading
+  readFile,
+  writeFile, // TODO remove
+} from "fs";
+
+readFile("a");
+"#;
+        assert_eq!(format(src), src);
+    }
+}
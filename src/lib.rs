@@ -1,6 +1,9 @@
-#![feature(extract_if)]
-
 mod config;
 mod format;
 mod plugin;
 mod utils;
+
+pub use config::{
+    convert_import_order, migrate_import_order, Configuration, ImportOrderConversion,
+};
+pub use plugin::ImporgHandler;
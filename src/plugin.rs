@@ -4,13 +4,14 @@ use anyhow::Result;
 use dprint_core::configuration::{
     get_unknown_property_diagnostics, ConfigKeyMap, GlobalConfiguration,
 };
+#[cfg(target_arch = "wasm32")]
 use dprint_core::generate_plugin_code;
 use dprint_core::plugins::{
     CheckConfigUpdatesMessage, ConfigChange, FileMatchingInfo, FormatResult, PluginInfo,
     PluginResolveConfigurationResult, SyncFormatRequest, SyncHostFormatRequest, SyncPluginHandler,
 };
 
-use crate::config::{resolve_config, Configuration};
+use crate::config::{migrate_config, resolve_config, Configuration};
 use crate::format::format_source;
 
 pub struct ImporgHandler;
@@ -39,8 +40,9 @@ impl SyncPluginHandler<Configuration> for ImporgHandler {
         _global: &GlobalConfiguration,
     ) -> PluginResolveConfigurationResult<Configuration> {
         let mut diagnostics = Vec::new();
+        let mut warnings = Vec::new();
 
-        let resolved = match resolve_config(&mut raw) {
+        let resolved = match resolve_config(&mut raw, &mut warnings) {
             Ok(it) => it,
             Err(err) => {
                 diagnostics.push(err);
@@ -48,6 +50,11 @@ impl SyncPluginHandler<Configuration> for ImporgHandler {
             }
         };
 
+        // dprint stops formatting on any diagnostic, so the warnings are opt-in
+        if resolved.strict {
+            diagnostics.extend(warnings);
+        }
+
         diagnostics.extend(get_unknown_property_diagnostics(raw));
 
         PluginResolveConfigurationResult {
@@ -69,9 +76,10 @@ impl SyncPluginHandler<Configuration> for ImporgHandler {
 
     fn check_config_updates(
         &self,
-        _message: CheckConfigUpdatesMessage,
+        message: CheckConfigUpdatesMessage,
     ) -> Result<Vec<ConfigChange>> {
-        Ok(vec![])
+        let old_version = message.old_version.as_deref();
+        Ok(migrate_config(&message.config, old_version))
     }
 
     fn format(
@@ -86,7 +94,9 @@ impl SyncPluginHandler<Configuration> for ImporgHandler {
 
         let source = std::str::from_utf8(source)?;
 
-        let mut output = format_source(request.config, request.file_path, source)?;
+        let config = request.config.resolve_for(request.file_path);
+
+        let mut output = format_source(&config, request.file_path, source)?;
         let mut output_range = None;
 
         if let Some(range) = request.range {
@@ -110,4 +120,47 @@ impl SyncPluginHandler<Configuration> for ImporgHandler {
     }
 }
 
+// The exports only link in Wasm, and the rest is built natively for tests
+#[cfg(target_arch = "wasm32")]
 generate_plugin_code!(ImporgHandler, ImporgHandler, Configuration);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn diagnostics(config: serde_json::Value) -> Vec<String> {
+        let raw: ConfigKeyMap = serde_json::from_value(config).unwrap();
+        let global = GlobalConfiguration::default();
+        let result = ImporgHandler.resolve_config(raw, &global);
+        result
+            .diagnostics
+            .into_iter()
+            .map(|it| it.message)
+            .collect()
+    }
+
+    #[test]
+    fn report_warnings_when_strict() {
+        let groups = json!([{ "include": ["<npm>"] }, { "include": [{ "packages": ["react"] }] }]);
+
+        assert!(diagnostics(json!({ "groups": groups })).is_empty());
+
+        let messages = diagnostics(json!({ "groups": groups, "strict": true }));
+        assert_eq!(
+            messages,
+            ["groups[1] is unreachable, all its imports are matched by groups[0]"]
+        );
+    }
+
+    #[test]
+    fn keep_migrate_from() {
+        let import_order = json!({ "newlines-between": "ignore" });
+        let config = json!({ "migrateFrom": { "importOrder": import_order } });
+        assert!(diagnostics(config).is_empty());
+
+        let config = json!({ "migrateFrom": { "importOrder": import_order }, "strict": true });
+        assert_eq!(diagnostics(config).len(), 1);
+    }
+}
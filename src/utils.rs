@@ -17,6 +17,16 @@ pub fn log(message: &str) {
     let _ = WasiPrintFd(1).write_all(message.as_bytes());
 }
 
+/// Returns the package name of a module, e.g. `@scope/pkg` for `@scope/pkg/sub/path`.
+pub fn package_name(module: &str) -> &str {
+    let segments = if module.starts_with('@') { 2 } else { 1 };
+
+    match module.match_indices('/').nth(segments - 1) {
+        Some((end, _)) => &module[..end],
+        None => module,
+    }
+}
+
 #[macro_export]
 macro_rules! re {
     ($re:expr) => {{